@@ -0,0 +1,147 @@
+//! `ascii` 模块的性能测试：对比按字符分配字符串再拼接的旧实现、逐字符判断的朴素扫描与当前按机器字扫描的实现。
+//!
+//! 运行：`cargo bench --bench ascii`，报告输出到 `target/criterion`；用 `--save-baseline <名称>` 和 `--baseline <名称>` 可以与之前的结果比较。
+
+use std::fmt::Write;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use utils_rust::ascii::{decode, encode, escape, EncodeMode, EncodeOptions, HtmlContext};
+
+/// 旧的 `decode` 实现：按 `;` 切分、去掉 `&#` 后逐段解析并拼接，只能处理完全由十进制数字编码组成的输入。
+fn legacy_decode(u: &str) -> String {
+    u.split(';')
+        .map(|item| {
+            let u = item.replace("&#", "");
+            match u.parse::<u32>().ok().and_then(char::from_u32) {
+                Some(x) => x.to_string(),
+                None => "".to_string(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("")
+}
+
+/// 旧的 `encode` 实现：每个字符分配一个字符串后拼接。
+fn legacy_encode(s: &str) -> String {
+    s.chars()
+        .map(|c| format!("&#{};", c as u32))
+        .collect::<Vec<String>>()
+        .join("")
+}
+
+/// 朴素的选择性编码：逐字符判断是否需要编码，不使用按机器字扫描的快速路径，输出与 [`EncodeOptions::encode`] 相同。
+fn naive_encode(s: &str, encodes: impl Fn(char) -> bool) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if encodes(c) {
+            let _ = write!(out, "&#{};", c as u32);
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn html_special(c: char) -> bool {
+    matches!(c, '<' | '>' | '&' | '"' | '\'')
+}
+
+fn non_ascii(c: char) -> bool {
+    !c.is_ascii()
+}
+
+/// ASCII 为主和中文为主的测试文本。
+fn corpora() -> [(&'static str, String); 2] {
+    [
+        (
+            "ascii",
+            "<p>The quick brown fox &amp; the lazy dog &lt;jump&gt; over fences.</p>\n"
+                .repeat(2_000),
+        ),
+        (
+            "cjk",
+            "<p>敏捷的棕色狐狸跳过了懒狗，&ldquo;测试&rdquo;文本&amp;实体。</p>\n".repeat(2_000),
+        ),
+    ]
+}
+
+/// 编码全部字符：旧实现与当前实现。
+fn bench_encode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode");
+    for (corpus, text) in &corpora() {
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        group.bench_with_input(BenchmarkId::new("legacy", corpus), text, |b, s| {
+            b.iter(|| legacy_encode(black_box(s)))
+        });
+        group.bench_with_input(BenchmarkId::new("current", corpus), text, |b, s| {
+            b.iter(|| encode(black_box(s)))
+        });
+    }
+    group.finish();
+}
+
+/// 选择性编码和按上下文转义：逐字符判断的朴素扫描与按机器字扫描的快速路径。
+fn bench_scan(c: &mut Criterion) {
+    let special = EncodeOptions::new().mode(EncodeMode::HtmlSpecial);
+    let ascii = EncodeOptions::new().mode(EncodeMode::NonAscii);
+    for (_, text) in &corpora() {
+        assert_eq!(naive_encode(text, html_special), special.encode(text));
+        assert_eq!(naive_encode(text, non_ascii), ascii.encode(text));
+    }
+
+    let mut group = c.benchmark_group("scan");
+    for (corpus, text) in &corpora() {
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("html-special/naive", corpus),
+            text,
+            |b, s| b.iter(|| naive_encode(black_box(s), html_special)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("html-special/swar", corpus),
+            text,
+            |b, s| b.iter(|| special.encode(black_box(s))),
+        );
+        group.bench_with_input(BenchmarkId::new("non-ascii/naive", corpus), text, |b, s| {
+            b.iter(|| naive_encode(black_box(s), non_ascii))
+        });
+        group.bench_with_input(BenchmarkId::new("non-ascii/swar", corpus), text, |b, s| {
+            b.iter(|| ascii.encode(black_box(s)))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("escape-text/swar", corpus),
+            text,
+            |b, s| b.iter(|| escape(black_box(s), HtmlContext::Text)),
+        );
+    }
+    group.finish();
+}
+
+/// 解码：旧实现只能解码完全由数字编码组成的输入，混合文本只测当前实现。
+fn bench_decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode");
+    for (corpus, text) in &corpora() {
+        let numeric = encode(text);
+        assert_eq!(legacy_decode(&numeric), decode(&numeric));
+        group.throughput(Throughput::Bytes(numeric.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::new("numeric/legacy", corpus),
+            &numeric,
+            |b, s| b.iter(|| legacy_decode(black_box(s))),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("numeric/current", corpus),
+            &numeric,
+            |b, s| b.iter(|| decode(black_box(s))),
+        );
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        group.bench_with_input(BenchmarkId::new("mixed/current", corpus), text, |b, s| {
+            b.iter(|| decode(black_box(s)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_encode, bench_scan, bench_decode);
+criterion_main!(benches);
@@ -0,0 +1,57 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// 由 `src/ascii/entities.txt` 生成按名称排序的命名实体表，以及按字符排序的首选名称表，供 `ascii` 模块二分查找；同时生成最长名称的长度，供流式解码器限制暂缓的输入。
+fn main() {
+    let source = "src/ascii/entities.txt";
+    println!("cargo:rerun-if-changed={source}");
+
+    let text = fs::read_to_string(source).expect("无法读取命名实体表");
+    let mut entities = Vec::new();
+    let mut names = Vec::new();
+    for line in text.lines() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut columns = line.split('\t');
+        let (Some(name), Some(points)) = (columns.next(), columns.next()) else {
+            panic!("命名实体表格式错误：{line}");
+        };
+        let value = points
+            .split(' ')
+            .map(|p| {
+                let p = u32::from_str_radix(p, 16).expect("码点格式错误");
+                char::from_u32(p).expect("码点不是合法字符")
+            })
+            .collect::<String>();
+        if columns.next() == Some("*") {
+            let c = value.chars().next().expect("首选名称必须对应单个字符");
+            names.push((c, name.trim_end_matches(';').to_string()));
+        }
+        entities.push((name.to_string(), value));
+    }
+    entities.sort();
+    names.sort();
+
+    let max_len = entities
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    // 只有依赖 `std` 的流式解码器使用最长名称的长度。
+    let mut out =
+        format!("#[cfg(feature = \"std\")]\npub(crate) const MAX_NAME_LEN: usize = {max_len};\n\n");
+    out.push_str("static ENTITIES: &[(&str, &str)] = &[\n");
+    for (name, value) in &entities {
+        out.push_str(&format!("    ({name:?}, {value:?}),\n"));
+    }
+    out.push_str("];\n\nstatic NAMES: &[(char, &str)] = &[\n");
+    for (c, name) in &names {
+        out.push_str(&format!("    ({c:?}, {name:?}),\n"));
+    }
+    out.push_str("];\n");
+
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("entities.rs");
+    fs::write(dest, out).expect("无法写入命名实体表");
+}
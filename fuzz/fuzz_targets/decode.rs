@@ -0,0 +1,36 @@
+//! 解码任意输入：不能 panic，输出长度和分配量与输入长度成线性关系。
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use utils_rust::ascii::{decode, decode_bytes, decode_cow, DecodeOptions, ErrorPolicy};
+use utils_rust_fuzz::bounded;
+
+/// 每个输入字节最多解码为 2 个字节：最长的展开是 `&nLt;`（5 字节）解码为 6 字节。
+const MAX_GROWTH: usize = 2;
+
+fuzz_target!(|data: &[u8]| {
+    // 非法的 UTF-8 序列（1 字节）会替换为 U+FFFD（3 字节）。
+    let decoded = bounded(3 * MAX_GROWTH * data.len(), || decode_bytes(data));
+    assert!(decoded.len() <= 3 * MAX_GROWTH * data.len());
+
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    let max_output = MAX_GROWTH * input.len();
+    let decoded = bounded(max_output, || decode(input));
+    assert!(decoded.len() <= MAX_GROWTH * input.len());
+    assert_eq!(decode_cow(input), decoded);
+
+    for policy in [
+        ErrorPolicy::Strict,
+        ErrorPolicy::Replace,
+        ErrorPolicy::Passthrough,
+        ErrorPolicy::Drop,
+    ] {
+        let options = DecodeOptions::new().policy(policy).attribute(true);
+        if let Ok(decoded) = bounded(max_output, || options.decode(input)) {
+            assert!(decoded.len() <= MAX_GROWTH * input.len());
+        }
+    }
+});
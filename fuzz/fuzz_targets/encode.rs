@@ -0,0 +1,46 @@
+//! 按任意选项编码任意文本：不能 panic，输出长度和分配量与输入长度成线性关系。
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use utils_rust::ascii::{encode, encode_cow, EncodeMode, EncodeOptions, EncodeStyle};
+use utils_rust_fuzz::bounded;
+
+/// 每个输入字节最多编码为 12 个字节：`&NewLine;`（9 字节）对应 1 字节的换行，`&CounterClockwiseContourIntegral;`（33 字节）对应 3 字节的字符。
+const MAX_GROWTH: usize = 12;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&selector, rest)) = data.split_first() else {
+        return;
+    };
+    let Ok(input) = std::str::from_utf8(rest) else {
+        return;
+    };
+    let max_output = MAX_GROWTH * input.len();
+
+    let encoded = bounded(max_output, || encode(input));
+    assert!(encoded.len() <= MAX_GROWTH * input.len());
+    assert_eq!(encode_cow(input), encoded);
+
+    let mut split = input.len().min(usize::from(selector >> 4));
+    while !input.is_char_boundary(split) {
+        split -= 1;
+    }
+    let (keep, text) = input.split_at(split);
+    let mode = match selector & 0b11 {
+        0 => EncodeMode::All,
+        1 => EncodeMode::HtmlSpecial,
+        2 => EncodeMode::NonAscii,
+        _ => EncodeMode::Except(keep),
+    };
+    let style = match (selector >> 2) & 0b11 {
+        0 => EncodeStyle::Decimal,
+        1 => EncodeStyle::HexLower,
+        2 => EncodeStyle::HexUpper,
+        _ => EncodeStyle::Named,
+    };
+    let options = EncodeOptions::new().mode(mode).style(style);
+    let encoded = bounded(max_output, || options.encode(text));
+    assert!(encoded.len() <= MAX_GROWTH * text.len());
+    assert_eq!(options.encode_cow(text), encoded);
+});
@@ -0,0 +1,39 @@
+//! 编码后再解码必须还原输入，严格模式下解码编码结果不能报错。
+//!
+//! `U+0000` 和被 Windows-1252 重映射的 C1 控制字符按 WHATWG 规则解码为其他字符，不参与检查。
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use utils_rust::ascii::{
+    decode, encode, DecodeOptions, EncodeMode, EncodeOptions, EncodeStyle, ErrorPolicy,
+};
+
+/// 编码后能解码还原的字符。
+fn representable(c: char) -> bool {
+    match c {
+        '\0' => false,
+        '\u{80}'..='\u{9F}' => matches!(c, '\u{81}' | '\u{8D}' | '\u{8F}' | '\u{90}' | '\u{9D}'),
+        _ => true,
+    }
+}
+
+fuzz_target!(|input: &str| {
+    if !input.chars().all(representable) {
+        return;
+    }
+    assert_eq!(decode(&encode(input)), input);
+
+    let strict = DecodeOptions::new().policy(ErrorPolicy::Strict);
+    for mode in [EncodeMode::All, EncodeMode::HtmlSpecial] {
+        for style in [
+            EncodeStyle::Decimal,
+            EncodeStyle::HexLower,
+            EncodeStyle::HexUpper,
+            EncodeStyle::Named,
+        ] {
+            let encoded = EncodeOptions::new().mode(mode).style(style).encode(input);
+            assert_eq!(strict.decode(&encoded).as_deref(), Ok(input));
+        }
+    }
+});
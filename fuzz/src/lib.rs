@@ -0,0 +1,52 @@
+//! `ascii` 模块的模糊测试目标共用的工具。
+//!
+//! 需要 nightly 工具链和 `cargo-fuzz`，在 `fuzz` 目录下运行：
+//!
+//! ```text
+//! cargo +nightly fuzz run decode corpus/decode seeds
+//! cargo +nightly fuzz run encode corpus/encode seeds
+//! cargo +nightly fuzz run round_trip corpus/round_trip seeds
+//! ```
+//!
+//! `seeds` 目录存放手工整理的初始语料（超长数字、`&#;`、`&#x;`、连续的 `&&#`、未结束的字符引用等），新发现的语料写入第一个目录 `corpus/<目标>`。
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// 记录当前分配量和峰值的分配器。
+struct CountingAlloc;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let now = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(now, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// 运行 `f` 并断言期间新增的峰值分配量与输出长度上限 `max_output` 成线性关系。
+///
+/// 上限考虑了中间结果以及字符串扩容时新旧缓冲区同时存在的情况。
+pub fn bounded<T>(max_output: usize, f: impl FnOnce() -> T) -> T {
+    let limit = 8 * max_output + 1024;
+    let base = ALLOCATED.load(Ordering::Relaxed);
+    PEAK.store(base, Ordering::Relaxed);
+    let result = f();
+    let used = PEAK.load(Ordering::Relaxed) - base;
+    assert!(used <= limit, "分配了 {used} 字节，上限为 {limit} 字节");
+    result
+}
@@ -0,0 +1,143 @@
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use core::str::{self, Utf8Error};
+
+use super::{
+    decode_cow_with, encode_cow_with, DecodeError, DecodeErrorKind, DecodeOptions, EncodeOptions,
+};
+
+/// 处理字节输入中非法 UTF-8 序列的方式。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Utf8Policy {
+    /// 将非法序列替换为 U+FFFD（`�`），与 [`String::from_utf8_lossy`] 一致。
+    #[default]
+    Lossy,
+    /// 遇到非法序列时返回错误。
+    Strict,
+}
+
+/// 按策略将字节转换为文本，合法的输入直接借用。
+fn to_str(bytes: &[u8], utf8: Utf8Policy) -> Result<Cow<'_, str>, Utf8Error> {
+    match utf8 {
+        Utf8Policy::Lossy => Ok(String::from_utf8_lossy(bytes)),
+        Utf8Policy::Strict => str::from_utf8(bytes).map(Cow::Borrowed),
+    }
+}
+
+/// 将文本处理结果转换回字节；结果借用自 `text` 且 `text` 借用自输入时，直接借用输入。
+fn to_bytes<'b>(text: &Cow<'b, str>, result: Cow<'_, str>) -> Cow<'b, [u8]> {
+    match (text, result) {
+        (&Cow::Borrowed(s), Cow::Borrowed(_)) => Cow::Borrowed(s.as_bytes()),
+        (_, result) => Cow::Owned(result.into_owned().into_bytes()),
+    }
+}
+
+/// 按选项编码字节输入。
+pub(crate) fn encode_bytes_with<'b>(
+    bytes: &'b [u8],
+    options: &EncodeOptions,
+) -> Result<Cow<'b, [u8]>, Utf8Error> {
+    let text = to_str(bytes, options.utf8)?;
+    let result = encode_cow_with(&text, options);
+    Ok(to_bytes(&text, result))
+}
+
+/// 按选项解码字节输入，严格模式下非法的 UTF-8 序列报告为 [`DecodeErrorKind::InvalidUtf8`]。
+pub(crate) fn decode_bytes_with<'b>(
+    bytes: &'b [u8],
+    options: &DecodeOptions,
+) -> Result<Cow<'b, [u8]>, DecodeError> {
+    let text = to_str(bytes, options.utf8).map_err(|e| {
+        let offset = e.valid_up_to();
+        let len = e.error_len().unwrap_or(bytes.len() - offset);
+        DecodeError {
+            offset,
+            reference: bytes[offset..offset + len].escape_ascii().to_string(),
+            kind: DecodeErrorKind::InvalidUtf8,
+        }
+    })?;
+    let result = decode_cow_with(&text, options)?;
+    Ok(to_bytes(&text, result))
+}
+
+/// 将字节形式的文本编码为 HTML 实体编码格式。
+///
+/// # 说明
+///
+/// 编码规则与 [`encode`](super::encode) 相同，输入中的非法 UTF-8 序列按 U+FFFD 编码。没有需要编码的字符时直接借用输入。需要在遇到非法序列时报错，请使用 [`EncodeOptions::encode_bytes`] 并设置 [`Utf8Policy::Strict`]。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::ascii::encode_bytes;
+/// assert_eq!(encode_bytes("测".as_bytes()), &b"&#27979;"[..]);
+/// assert_eq!(encode_bytes(b"\xff"), &b"&#65533;"[..]);
+/// ```
+pub fn encode_bytes(bytes: &[u8]) -> Cow<'_, [u8]> {
+    encode_bytes_with(bytes, &EncodeOptions::new()).unwrap_or_default()
+}
+
+/// 解码字节形式的 HTML 实体编码文本，返回 UTF-8 字节。
+///
+/// # 说明
+///
+/// 解码规则与 [`decode`](super::decode) 相同，输入中的非法 UTF-8 序列替换为 U+FFFD。输入中没有字符引用且是合法的 UTF-8 时直接借用输入。需要在遇到非法序列时报错，请使用 [`DecodeOptions::decode_bytes`] 并设置 [`Utf8Policy::Strict`]。
+///
+/// # 示例
+///
+/// ```rust
+/// # use std::borrow::Cow;
+/// # use utils_rust::ascii::decode_bytes;
+/// assert_eq!(decode_bytes(b"&#27979; \xff"), "测 \u{FFFD}".as_bytes());
+/// assert!(matches!(decode_bytes(b"plain"), Cow::Borrowed(_)));
+/// ```
+pub fn decode_bytes(bytes: &[u8]) -> Cow<'_, [u8]> {
+    decode_bytes_with(bytes, &DecodeOptions::new()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ascii::{EncodeMode, ErrorPolicy};
+
+    #[test]
+    fn test_encode_bytes() {
+        assert_eq!(encode_bytes(b"a\xe6\xb5"), &b"&#97;&#65533;"[..]);
+
+        let options = EncodeOptions::new().mode(EncodeMode::HtmlSpecial);
+        assert!(matches!(
+            options.encode_bytes(b"plain"),
+            Ok(Cow::Borrowed(b"plain"))
+        ));
+        assert_eq!(
+            options.encode_bytes(b"<\xff>").unwrap(),
+            "&#60;\u{FFFD}&#62;".as_bytes()
+        );
+        assert!(matches!(options.encode_bytes(b"\xff"), Ok(Cow::Owned(_))));
+
+        let err = options
+            .utf8(Utf8Policy::Strict)
+            .encode_bytes(b"ok\xff")
+            .unwrap_err();
+        assert_eq!(err.valid_up_to(), 2);
+    }
+
+    #[test]
+    fn test_decode_bytes() {
+        assert_eq!(decode_bytes(b"&lt;\xc0&gt;"), "<\u{FFFD}>".as_bytes());
+        assert!(matches!(decode_bytes(b"\xff"), Cow::Owned(_)));
+
+        let options = DecodeOptions::new().utf8(Utf8Policy::Strict);
+        assert_eq!(options.decode_bytes(b"&amp;").unwrap(), &b"&"[..]);
+        assert!(options.decode_bytes("测试\u{FFFD}".as_bytes()).is_ok());
+
+        let err = options.decode_bytes(b"&amp; \xe6\xb5").unwrap_err();
+        assert_eq!(err.offset, 6);
+        assert_eq!(err.reference, "\\xe6\\xb5");
+        assert_eq!(err.kind, DecodeErrorKind::InvalidUtf8);
+
+        let options = options.policy(ErrorPolicy::Strict);
+        let err = options.decode_bytes(b"&#0;").unwrap_err();
+        assert_eq!(err.kind, DecodeErrorKind::NullCharacter);
+    }
+}
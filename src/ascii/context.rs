@@ -0,0 +1,190 @@
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use super::scan;
+use crate::percent::{self, EncodeSet};
+
+/// 转义文本时所处的 HTML 上下文，不同上下文需要不同的转义规则（参考 OWASP XSS 防护备忘单）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HtmlContext {
+    /// 元素内容，如 `<div>…</div>`；转义 `&`、`<`、`>`、`"`、`'`。
+    Text,
+    /// 带引号的属性值，如 `<div title="…">`；转义规则与 [`HtmlContext::Text`] 相同，单双引号均可使用。
+    QuotedAttribute,
+    /// 不带引号的属性值，如 `<div title=…>`；码点小于 256 的非字母数字字符均转义为 `&#xHH;`。
+    UnquotedAttribute,
+    /// `<script>` 中带引号的 JavaScript 字符串，如 `var a = '…'`；码点小于 256 的非字母数字字符转义为 `\xHH`，U+2028、U+2029 转义为 `\uHHHH`。
+    Script,
+    /// `<style>` 或 `style` 属性中的 CSS 属性值，如 `color: …`；码点小于 256 的非字母数字字符转义为 `\HH `。
+    Style,
+    /// URL 中的参数值或路径片段，如 `href="/search?q=…"`；除 RFC 3986 非保留字符外，均按 UTF-8 字节转义为 `%HH`，与 [`percent::encode`] 一致。
+    Url,
+}
+
+impl HtmlContext {
+    /// 判断字符在当前上下文中是否需要转义。
+    fn escapes(self, c: char) -> bool {
+        match self {
+            HtmlContext::Text | HtmlContext::QuotedAttribute => {
+                matches!(c, '&' | '<' | '>' | '"' | '\'')
+            }
+            HtmlContext::UnquotedAttribute | HtmlContext::Style => {
+                (c as u32) < 256 && !c.is_ascii_alphanumeric()
+            }
+            HtmlContext::Script => {
+                ((c as u32) < 256 && !c.is_ascii_alphanumeric())
+                    || matches!(c, '\u{2028}' | '\u{2029}')
+            }
+            HtmlContext::Url => EncodeSet::Component.encodes(c),
+        }
+    }
+
+    /// 将需要转义的字符按当前上下文的规则写入 `out`。
+    fn write_escaped<W: Write + ?Sized>(self, c: char, out: &mut W) -> fmt::Result {
+        match self {
+            HtmlContext::Text | HtmlContext::QuotedAttribute => out.write_str(match c {
+                '&' => "&amp;",
+                '<' => "&lt;",
+                '>' => "&gt;",
+                '"' => "&quot;",
+                _ => "&#x27;",
+            }),
+            HtmlContext::UnquotedAttribute => write!(out, "&#x{:X};", c as u32),
+            HtmlContext::Script if (c as u32) < 256 => write!(out, "\\x{:02X}", c as u32),
+            HtmlContext::Script => write!(out, "\\u{:04X}", c as u32),
+            HtmlContext::Style => write!(out, "\\{:X} ", c as u32),
+            HtmlContext::Url => percent::encode_to(c.encode_utf8(&mut [0; 4]), out),
+        }
+    }
+
+    /// 查找第一个需要转义的字符的位置，元素内容和带引号的属性值按机器字批量扫描。
+    fn find(self, s: &str) -> Option<usize> {
+        match self {
+            HtmlContext::Text | HtmlContext::QuotedAttribute => {
+                scan::find_html_special(s.as_bytes())
+            }
+            _ => s
+                .char_indices()
+                .find(|&(_, c)| self.escapes(c))
+                .map(|(i, _)| i),
+        }
+    }
+}
+
+/// 转义的核心实现，将结果写入 `out`，无需转义的文本成段写入。
+fn escape_into<W: Write + ?Sized>(s: &str, context: HtmlContext, out: &mut W) -> fmt::Result {
+    let mut start = 0;
+    while let Some(i) = context.find(&s[start..]) {
+        let i = start + i;
+        out.write_str(&s[start..i])?;
+        let c = s[i..].chars().next().unwrap_or_default();
+        context.write_escaped(c, out)?;
+        start = i + c.len_utf8();
+    }
+    out.write_str(&s[start..])
+}
+
+/// 按 HTML 上下文转义字符串，使其可以安全地嵌入到对应位置。
+///
+/// # 说明
+///
+/// 与 [`encode`](super::encode) 不加区分地编码字符不同，这个函数按 [`HtmlContext`] 选择对应位置所需的转义规则，如元素内容使用 `&lt;`，`<script>` 中的字符串使用 `\x3C`，URL 参数使用 `%3C`。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::ascii::{escape, HtmlContext};
+/// let input = "<a href='x'>测</a>";
+/// assert_eq!(escape(input, HtmlContext::Text), "&lt;a href=&#x27;x&#x27;&gt;测&lt;/a&gt;");
+/// assert_eq!(escape("a b=c", HtmlContext::UnquotedAttribute), "a&#x20;b&#x3D;c");
+/// assert_eq!(escape("';alert(1)//", HtmlContext::Script), "\\x27\\x3Balert\\x281\\x29\\x2F\\x2F");
+/// assert_eq!(escape("red;}", HtmlContext::Style), "red\\3B \\7D ");
+/// assert_eq!(escape("a&b=测", HtmlContext::Url), "a%26b%3D%E6%B5%8B");
+/// ```
+///
+/// # 注意事项
+///
+/// - [`HtmlContext::Script`] 和 [`HtmlContext::Style`] 只适用于带引号的字符串或属性值，不能用于转义任意的代码。
+/// - [`HtmlContext::Url`] 只转义 URL 的组成部分，不会校验 `javascript:` 等危险的协议，完整的 URL 需要另行校验。
+pub fn escape(s: &str, context: HtmlContext) -> String {
+    let mut out = String::with_capacity(s.len());
+    let _ = escape_into(s, context, &mut out);
+    out
+}
+
+/// 按 HTML 上下文转义字符串并写入 `out`，不分配中间字符串。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::ascii::{escape_to, HtmlContext};
+/// let mut html = String::from("<p title=\"");
+/// escape_to("\"引号\"", HtmlContext::QuotedAttribute, &mut html).unwrap();
+/// html.push_str("\">");
+/// assert_eq!(html, "<p title=\"&quot;引号&quot;\">");
+/// ```
+pub fn escape_to<W: Write + ?Sized>(s: &str, context: HtmlContext, out: &mut W) -> fmt::Result {
+    escape_into(s, context, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ascii::decode;
+
+    #[test]
+    fn test_escape_text() {
+        let input = "<script>alert(\"x\" & 'y')</script> 测试";
+        let escaped = escape(input, HtmlContext::Text);
+        assert_eq!(
+            escaped,
+            "&lt;script&gt;alert(&quot;x&quot; &amp; &#x27;y&#x27;)&lt;/script&gt; 测试"
+        );
+        assert_eq!(decode(&escaped), input);
+        assert_eq!(escape(input, HtmlContext::QuotedAttribute), escaped);
+    }
+
+    #[test]
+    fn test_escape_unquoted_attribute() {
+        let escaped = escape("x onload=alert(1) 测\t", HtmlContext::UnquotedAttribute);
+        assert_eq!(
+            escaped,
+            "x&#x20;onload&#x3D;alert&#x28;1&#x29;&#x20;测&#x9;"
+        );
+        assert!(!escaped.contains([' ', '=', '\t', '>']));
+    }
+
+    #[test]
+    fn test_escape_script() {
+        assert_eq!(
+            escape("</script>\u{2028}测", HtmlContext::Script),
+            "\\x3C\\x2Fscript\\x3E\\u2028测"
+        );
+        assert_eq!(escape("\n\"é", HtmlContext::Script), "\\x0A\\x22\\xE9");
+    }
+
+    #[test]
+    fn test_escape_style() {
+        assert_eq!(
+            escape("expression(alert(1))", HtmlContext::Style),
+            "expression\\28 alert\\28 1\\29 \\29 "
+        );
+    }
+
+    #[test]
+    fn test_escape_url() {
+        assert_eq!(
+            escape("a b&c=d/e?f#g~h-i_j.k", HtmlContext::Url),
+            "a%20b%26c%3Dd%2Fe%3Ff%23g~h-i_j.k"
+        );
+        assert_eq!(escape("测👍", HtmlContext::Url), "%E6%B5%8B%F0%9F%91%8D");
+    }
+
+    #[test]
+    fn test_escape_to() {
+        let mut out = String::new();
+        escape_to("<", HtmlContext::Text, &mut out).unwrap();
+        escape_to("<", HtmlContext::Url, &mut out).unwrap();
+        assert_eq!(out, "&lt;%3C");
+    }
+}
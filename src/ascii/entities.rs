@@ -0,0 +1,88 @@
+// 由 build.rs 根据 `entities.txt` 生成：
+// - `pub(crate) const MAX_NAME_LEN: usize`，最长的实体名称（含 `;`）的字节数，仅在启用 `std` 时生成；
+// - `static ENTITIES: &[(&str, &str)]`，按名称排序；
+// - `static NAMES: &[(char, &str)]`，编码时各字符首选的名称（不含 `;`），按字符排序。
+include!(concat!(env!("OUT_DIR"), "/entities.rs"));
+
+/// 不带分号的遗留实体名称的最大长度（如 `middot`、`frac12`）。
+const LEGACY_MAX_LEN: usize = 6;
+
+/// 按完整名称查找命名实体，名称不含前导 `&`，带分号的形式需包含 `;`。
+pub(crate) fn lookup(name: &str) -> Option<&'static str> {
+    ENTITIES
+        .binary_search_by(|(n, _)| n.cmp(&name))
+        .ok()
+        .map(|i| ENTITIES[i].1)
+}
+
+/// 查找编码字符时首选的实体名称，名称不含前导 `&` 和结尾 `;`。
+pub(crate) fn name_of(c: char) -> Option<&'static str> {
+    NAMES
+        .binary_search_by(|(n, _)| n.cmp(&c))
+        .ok()
+        .map(|i| NAMES[i].1)
+}
+
+/// 解析 `&` 之后的命名实体，返回实体对应的字符串及消耗的字节数。
+///
+/// 优先匹配带分号的完整名称；否则按 HTML 规范回退到最长的不带分号的遗留名称（如 `&amp`、`&copy`）。
+pub(crate) fn parse(input: &str) -> Option<(&'static str, usize)> {
+    let len = input.bytes().take_while(u8::is_ascii_alphanumeric).count();
+    if len == 0 {
+        return None;
+    }
+    if input[len..].starts_with(';') {
+        if let Some(value) = lookup(&input[..=len]) {
+            return Some((value, len + 1));
+        }
+    }
+    (1..=len.min(LEGACY_MAX_LEN))
+        .rev()
+        .find_map(|n| lookup(&input[..n]).map(|value| (value, n)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_sorted() {
+        assert_eq!(ENTITIES.len(), 2231);
+        #[cfg(feature = "std")]
+        assert_eq!(MAX_NAME_LEN, "CounterClockwiseContourIntegral;".len());
+        assert!(ENTITIES.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(NAMES.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(lookup("amp;"), Some("&"));
+        assert_eq!(lookup("amp"), Some("&"));
+        assert_eq!(lookup("hellip;"), Some("…"));
+        assert_eq!(lookup("hellip"), None);
+        assert_eq!(lookup("NotEqualTilde;"), Some("\u{2242}\u{338}"));
+    }
+
+    #[test]
+    fn test_name_of() {
+        assert_eq!(name_of('&'), Some("amp"));
+        assert_eq!(name_of('<'), Some("lt"));
+        assert_eq!(name_of('©'), Some("copy"));
+        assert_eq!(name_of('\u{a0}'), Some("nbsp"));
+        assert_eq!(name_of('…'), Some("hellip"));
+        assert_eq!(name_of('a'), None);
+        for (c, name) in NAMES {
+            assert_eq!(lookup(&format!("{name};")), Some(c.to_string().as_str()));
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("amp;rest"), Some(("&", 4)));
+        assert_eq!(parse("amprest"), Some(("&", 3)));
+        assert_eq!(parse("notit;"), Some(("¬", 3)));
+        assert_eq!(parse("notin;"), Some(("∉", 6)));
+        assert_eq!(parse("unknown;"), None);
+        assert_eq!(parse(";"), None);
+    }
+}
@@ -0,0 +1,73 @@
+use alloc::string::String;
+use core::error::Error;
+use core::fmt;
+
+/// 字符引用解析失败的原因。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeErrorKind {
+    /// `&#` 或 `&#x` 之后没有有效的数字。
+    InvalidDigits,
+    /// 码点为 `0`。
+    NullCharacter,
+    /// 码点超出 Unicode 范围（大于 `0x10FFFF`）。
+    OutOfRange,
+    /// 码点位于 UTF-16 代理项区间（`0xD800..=0xDFFF`）。
+    Surrogate,
+    /// 字符引用缺少结尾的 `;`。
+    MissingSemicolon,
+    /// `&` 之后的字母数字以 `;` 结尾，但不是已知的实体名称，如 `&foo;`。
+    UnknownName,
+    /// 字节输入中存在非法的 UTF-8 序列。
+    InvalidUtf8,
+}
+
+impl fmt::Display for DecodeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DecodeErrorKind::InvalidDigits => "缺少有效数字",
+            DecodeErrorKind::NullCharacter => "码点为空字符",
+            DecodeErrorKind::OutOfRange => "码点超出 Unicode 范围",
+            DecodeErrorKind::Surrogate => "码点为 UTF-16 代理项",
+            DecodeErrorKind::MissingSemicolon => "缺少结尾的分号",
+            DecodeErrorKind::UnknownName => "不是已知的实体名称",
+            DecodeErrorKind::InvalidUtf8 => "不是有效的 UTF-8",
+        })
+    }
+}
+
+/// 解码 HTML 实体编码时遇到的错误。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::ascii::{try_decode, DecodeErrorKind};
+/// let err = try_decode("ok &#xD800; bad").unwrap_err();
+/// assert_eq!(err.offset, 3);
+/// assert_eq!(err.reference, "&#xD800;");
+/// assert_eq!(err.kind, DecodeErrorKind::Surrogate);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    /// 出错的字符引用在输入中的字节偏移（指向 `&`）。
+    pub offset: usize,
+    /// 出错的字符引用原文；非法的 UTF-8 序列以 `\xHH` 形式表示。
+    pub reference: String,
+    /// 出错原因。
+    pub kind: DecodeErrorKind,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let what = match self.kind {
+            DecodeErrorKind::InvalidUtf8 => "字节序列",
+            _ => "字符引用",
+        };
+        write!(
+            f,
+            "第 {} 字节处的{what} `{}` 无效：{}",
+            self.offset, self.reference, self.kind
+        )
+    }
+}
+
+impl Error for DecodeError {}
@@ -1,44 +1,369 @@
-use std::num::ParseIntError;
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use core::fmt::{self, Write};
+use core::num::IntErrorKind;
 
+mod bytes;
+mod context;
+mod entities;
+mod error;
+mod options;
+mod scan;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "std")]
+mod stream;
+
+pub use crate::codec::ErrorPolicy;
+pub use bytes::{decode_bytes, encode_bytes, Utf8Policy};
+pub use context::{escape, escape_to, HtmlContext};
+pub use error::{DecodeError, DecodeErrorKind};
+pub use options::{DecodeOptions, EncodeMode, EncodeOptions, EncodeStyle};
+#[cfg(feature = "std")]
+pub use stream::{EntityDecoder, EntityEncoder};
+
+/// `&` 之后解析出的字符引用。
 #[derive(Debug, PartialEq)]
+enum Reference {
+    Char(char),
+    Named(&'static str),
+}
+
+impl Reference {
+    fn write_to<W: Write + ?Sized>(&self, out: &mut W) -> fmt::Result {
+        match self {
+            Reference::Char(c) => out.write_char(*c),
+            Reference::Named(s) => out.write_str(s),
+        }
+    }
+}
+
+/// 解码核心实现可能遇到的错误。
+#[derive(Debug)]
 enum Error {
-    Int(ParseIntError),
-    Unicode(u32),
+    Decode(DecodeError),
+    Fmt(fmt::Error),
+}
+
+impl From<fmt::Error> for Error {
+    fn from(e: fmt::Error) -> Self {
+        Error::Fmt(e)
+    }
+}
+
+/// 单个字符引用的解析结果。
+#[derive(Debug, PartialEq)]
+enum Parsed {
+    /// 合法的字符引用。
+    Valid(Reference),
+    /// 语法不完整的字符引用；非严格模式下若有替代值则解码，否则按普通文本保留。
+    Malformed(DecodeErrorKind, Option<Reference>),
+    /// 码点不合法的数字引用。
+    Invalid(DecodeErrorKind),
+}
+
+/// WHATWG 规范中 `&#128;`～`&#159;` 对应的 Windows-1252 字符，规范未定义的位置保持原码点。
+const WINDOWS_1252: [char; 32] = [
+    '\u{20AC}', '\u{81}', '\u{201A}', '\u{192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2C6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8D}', '\u{17D}', '\u{8F}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2DC}', '\u{2122}', '\u{161}', '\u{203A}', '\u{153}', '\u{9D}', '\u{17E}', '\u{178}',
+];
+
+/// 按 WHATWG 数字字符引用规则将数字转换为字符：`0`、代理项和超出范围的码点视为错误，`0x80..=0x9F` 映射为 Windows-1252 字符。
+fn parse_unicode(input: &str) -> Result<char, DecodeErrorKind> {
+    let unicode = match input.strip_prefix(['x', 'X']) {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => input.parse::<u32>(),
+    }
+    .map_err(|e| match e.kind() {
+        IntErrorKind::PosOverflow => DecodeErrorKind::OutOfRange,
+        _ => DecodeErrorKind::InvalidDigits,
+    })?;
+    match unicode {
+        0 => Err(DecodeErrorKind::NullCharacter),
+        0x80..=0x9F => Ok(WINDOWS_1252[unicode as usize - 0x80]),
+        0xD800..=0xDFFF => Err(DecodeErrorKind::Surrogate),
+        _ => char::from_u32(unicode).ok_or(DecodeErrorKind::OutOfRange),
+    }
+}
+
+/// 解析 `&#` 之后的数字编码（如 `27979;` 或 `x6D4B;`），返回解析结果及消耗的字节数（含 `#`）。
+fn parse_numeric(body: &str) -> (Parsed, usize) {
+    let hex = body.starts_with(['x', 'X']);
+    let start = usize::from(hex);
+    let is_digit = if hex {
+        u8::is_ascii_hexdigit
+    } else {
+        u8::is_ascii_digit
+    };
+    let end = start + body[start..].bytes().take_while(is_digit).count();
+    if end == start {
+        return (
+            Parsed::Malformed(DecodeErrorKind::InvalidDigits, None),
+            end + 1,
+        );
+    }
+    // 与浏览器一致，缺少结尾 `;` 的数字引用同样解码。
+    let terminated = body[end..].starts_with(';');
+    let len = end + 1 + usize::from(terminated);
+    let parsed = match parse_unicode(&body[..end]) {
+        Ok(c) if terminated => Parsed::Valid(Reference::Char(c)),
+        Ok(c) => Parsed::Malformed(DecodeErrorKind::MissingSemicolon, Some(Reference::Char(c))),
+        Err(kind) => Parsed::Invalid(kind),
+    };
+    (parsed, len)
+}
+
+/// 按 HTML 分词器的字符引用状态机解析 `&` 之后的内容，返回解析结果及消耗的字节数；不构成字符引用时返回 `None`。
+///
+/// `attribute` 为 `true` 时按属性值的规则解析：缺少 `;` 的实体后紧跟 `=` 或字母数字时（如 `&notit`、`&copy=`）不构成字符引用。
+fn parse_reference(input: &str, attribute: bool) -> Option<(Parsed, usize)> {
+    if let Some(body) = input.strip_prefix('#') {
+        return Some(parse_numeric(body));
+    }
+    let Some((value, len)) = entities::parse(input) else {
+        // 歧义的 `&`：字母数字以 `;` 结尾时报告未知的实体名称，其余情况按普通文本处理。
+        let name = input.bytes().take_while(u8::is_ascii_alphanumeric).count();
+        let unknown = name > 0 && input[name..].starts_with(';');
+        return unknown.then_some((
+            Parsed::Malformed(DecodeErrorKind::UnknownName, None),
+            name + 1,
+        ));
+    };
+    let reference = Reference::Named(value);
+    if input[..len].ends_with(';') {
+        return Some((Parsed::Valid(reference), len));
+    }
+    if attribute && input[len..].starts_with(|c: char| c == '=' || c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    Some((
+        Parsed::Malformed(DecodeErrorKind::MissingSemicolon, Some(reference)),
+        len,
+    ))
+}
+
+/// 按选项解码并返回新分配的字符串。
+fn decode_with(u: &str, options: &DecodeOptions) -> Result<String, DecodeError> {
+    let mut out = String::with_capacity(u.len());
+    decode_string(u, options, &mut out)?;
+    Ok(out)
 }
 
-fn parse_unicode(input: &str) -> Result<char, Error> {
-    let unicode = u32::from_str_radix(input, 10).map_err(Error::Int)?;
-    char::from_u32(unicode).ok_or_else(|| Error::Unicode(unicode))
+/// 按选项解码；输入中没有 `&` 时直接借用输入。
+fn decode_cow_with<'u>(u: &'u str, options: &DecodeOptions) -> Result<Cow<'u, str>, DecodeError> {
+    if u.contains('&') {
+        decode_with(u, options).map(Cow::Owned)
+    } else {
+        Ok(Cow::Borrowed(u))
+    }
+}
+
+/// 按选项解码并将结果追加到 `out`，写入 `String` 不会出现格式化错误。
+fn decode_string(u: &str, options: &DecodeOptions, out: &mut String) -> Result<(), DecodeError> {
+    decode_into(u, options, out).map_err(|e| match e {
+        Error::Decode(e) => e,
+        Error::Fmt(_) => unreachable!("写入 String 不会失败"),
+    })
+}
+
+/// 解码的核心实现，将结果写入 `out`；严格模式下遇到第一个不合规的字符引用即返回错误。
+fn decode_into<W: Write + ?Sized>(
+    u: &str,
+    options: &DecodeOptions,
+    out: &mut W,
+) -> Result<(), Error> {
+    let mut rest = u;
+    while let Some(i) = rest.find('&') {
+        out.write_str(&rest[..i])?;
+        let offset = u.len() - rest.len() + i;
+        rest = &rest[i + 1..];
+        let Some((parsed, len)) = parse_reference(rest, options.attribute) else {
+            out.write_char('&')?;
+            continue;
+        };
+        let reference = &u[offset..=offset + len];
+        match (parsed, options.policy) {
+            (Parsed::Malformed(kind, _) | Parsed::Invalid(kind), ErrorPolicy::Strict) => {
+                return Err(Error::Decode(DecodeError {
+                    offset,
+                    reference: reference.to_string(),
+                    kind,
+                }));
+            }
+            (Parsed::Valid(r) | Parsed::Malformed(_, Some(r)), _) => r.write_to(out)?,
+            (Parsed::Malformed(_, None), _) => {
+                out.write_char('&')?;
+                continue;
+            }
+            (Parsed::Invalid(_), ErrorPolicy::Replace) => {
+                out.write_char(char::REPLACEMENT_CHARACTER)?
+            }
+            (Parsed::Invalid(_), ErrorPolicy::Passthrough) => out.write_str(reference)?,
+            (Parsed::Invalid(_), ErrorPolicy::Drop) => {}
+        }
+        rest = &rest[len..];
+    }
+    out.write_str(rest)?;
+    Ok(())
 }
 
 /// `decode` 将包含 HTML 实体编码的字符串转换为其对应的原始字符。
 ///
 /// # 说明
 ///
-/// 这个函数接受一个包含 HTML 实体编码的字符串，并将其中的每个编码（如 `&#27979;`）转换为其对应的 Unicode 字符。函数假设所有的编码都以 `&#` 开始，并以 `;` 结束。若遇到解析错误，则将错误的部分转换为空字符串。
+/// 这个函数扫描输入字符串中的实体编码（如 `&#27979;`、`&#x6D4B;` 或 `&amp;`），将其转换为对应的 Unicode 字符，编码之外的普通文本原样保留。数字编码以 `&#` 开始，通常以 `;` 结束；与浏览器一致，缺少 `;` 的数字编码（如 `&#27979`）同样解码。若遇到无法解析的数字编码（如码点超出范围），则与浏览器一致地将该编码转换为 U+FFFD（`�`）。
+///
+/// 与 HTML 规范一致，`&#` 后紧跟 `x` 或 `X` 时按十六进制解析，否则按十进制解析。
+///
+/// 数字编码遵循 WHATWG 规范：`&#0;`、代理项（如 `&#xD800;`）和超出 Unicode 范围的码点转换为 U+FFFD，`&#128;`～`&#159;` 按 Windows-1252 映射（如 `&#150;` 转换为 `–`）。
+///
+/// 命名实体支持 WHATWG 规范中的全部名称，包括允许省略分号的遗留名称（如 `&amp`、`&copy`）。解析过程遵循 HTML 分词器的字符引用状态机：取最长的匹配名称，因此 `&notin;` 解码为 `∉`，`&notit;` 解码为 `¬it;`。
 ///
 /// # 示例
 ///
 /// ```rust
 /// # use utils_rust::ascii::decode;
 /// assert_eq!( decode("&#27979;&#35797;"), "测试");
+/// assert_eq!( decode("&#x6D4B;&#X8BD5;"), "测试");
+/// assert_eq!( decode("&lt;&eacute;&hellip;&copy"), "<é…©");
+/// assert_eq!( decode("Hello &#27979; world"), "Hello 测 world");
+/// assert_eq!( decode("&#150;&#0;"), "–\u{FFFD}");
+/// assert_eq!( decode("&#27979&#x8BD5 &notit;"), "测试 ¬it;");
 /// ```
 ///
 /// # 注意事项
 ///
-/// - 这个函数假设输入的 HTML 实体编码字符串中的所有编码都是有效的，并且以 `&#` 开始且以 `;` 结束。
-/// - 对于无法解析的编码，函数将返回空字符串。
+/// - 不构成实体编码的文本（如单独的 `&`、未知的名称或 `&#` 后没有数字）会原样保留。
+/// - 按文本内容的规则解码；解码 HTML 属性值时请使用 [`DecodeOptions::attribute`]。
+/// - 对于格式正确但无法解析的数字编码，函数将返回 U+FFFD；需要其他处理方式时，请使用 [`DecodeOptions`]。
+/// - 需要得知解析失败的位置和原因时，请使用 [`try_decode`]。
 pub fn decode(u: &str) -> String {
-    u.split(';')
-        .map(|item| {
-            let u = item.replace("&#", "");
-            match parse_unicode(&u) {
-                Ok(x) => x.to_string(),
-                Err(_) => "".to_string(),
-            }
-        })
-        .collect::<Vec<String>>()
-        .join("")
+    decode_with(u, &DecodeOptions::new()).unwrap_or_default()
+}
+
+/// `try_decode` 以严格模式解码 HTML 实体编码，遇到不合规的字符引用时返回 [`DecodeError`]。
+///
+/// # 说明
+///
+/// 解码规则与 [`decode`] 相同，但不会静默替换或保留有问题的字符引用：数字编码缺少有效数字、码点为 `0`、码点超出范围、码点为代理项，任何缺少结尾 `;` 的字符引用（包括 `&amp` 这样的遗留名称），以及以 `;` 结尾的未知实体名称（如 `&foo;`）都会被视为错误。错误中包含出错位置的字节偏移、出错的原文及原因。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::ascii::{try_decode, DecodeErrorKind};
+/// assert_eq!(try_decode("a &lt; &#27979;").unwrap(), "a < 测");
+///
+/// let err = try_decode("&#x110000;").unwrap_err();
+/// assert_eq!(err.kind, DecodeErrorKind::OutOfRange);
+/// assert_eq!(err.to_string(), "第 0 字节处的字符引用 `&#x110000;` 无效：码点超出 Unicode 范围");
+///
+/// assert_eq!(try_decode("AT&T & &foo").unwrap(), "AT&T & &foo");
+/// assert_eq!(try_decode("&foo;").unwrap_err().kind, DecodeErrorKind::UnknownName);
+/// ```
+///
+/// # 注意事项
+///
+/// - 单独的 `&`（如 `a & b`）以及不以 `;` 结尾的未知名称（如 `&foo`）不构成字符引用，会原样保留而不会报错；未知名称后紧跟 `;` 时报告 [`DecodeErrorKind::UnknownName`]。
+/// - 只报告遇到的第一个错误。
+pub fn try_decode(u: &str) -> Result<String, DecodeError> {
+    decode_with(u, &DecodeOptions::new().policy(ErrorPolicy::Strict))
+}
+
+/// 将 HTML 实体编码解码后写入 `out`，不分配中间字符串。
+///
+/// # 说明
+///
+/// 解码规则与 [`decode`] 相同，只在写入 `out` 失败时返回错误，适合在循环中复用同一个缓冲区。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::ascii::decode_to;
+/// let mut out = String::from("结果：");
+/// decode_to("&#27979;&#35797;", &mut out).unwrap();
+/// assert_eq!(out, "结果：测试");
+/// ```
+pub fn decode_to<W: Write + ?Sized>(u: &str, out: &mut W) -> fmt::Result {
+    decode_into(u, &DecodeOptions::new(), out).map_err(|_| fmt::Error)
+}
+
+/// 解码 HTML 实体编码，输入中没有任何字符引用时直接借用输入而不分配内存。
+///
+/// # 示例
+///
+/// ```rust
+/// # use std::borrow::Cow;
+/// # use utils_rust::ascii::decode_cow;
+/// assert!(matches!(decode_cow("plain text"), Cow::Borrowed("plain text")));
+/// assert_eq!(decode_cow("&lt;p&gt;"), "<p>");
+/// ```
+pub fn decode_cow(u: &str) -> Cow<'_, str> {
+    decode_cow_with(u, &DecodeOptions::new()).unwrap_or_default()
+}
+
+/// 按选项编码并返回新分配的字符串。
+fn encode_with(s: &str, options: &EncodeOptions) -> String {
+    let mut out = String::with_capacity(s.len());
+    let _ = encode_into(s, options, &mut out);
+    out
+}
+
+/// 按选项编码；没有需要编码的字符时直接借用输入。
+fn encode_cow_with<'s>(s: &'s str, options: &EncodeOptions) -> Cow<'s, str> {
+    if options.mode.find(s).is_some() {
+        Cow::Owned(encode_with(s, options))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+/// 编码的核心实现，将结果写入 `out`，只编码当前模式下需要编码的字符，其余文本成段写入。
+fn encode_into<W: Write + ?Sized>(s: &str, options: &EncodeOptions, out: &mut W) -> fmt::Result {
+    let mut start = 0;
+    while let Some(i) = options.mode.find(&s[start..]) {
+        let i = start + i;
+        out.write_str(&s[start..i])?;
+        let c = s[i..].chars().next().unwrap_or_default();
+        start = i + c.len_utf8();
+        let name = match options.style {
+            EncodeStyle::Named => entities::name_of(c),
+            _ => None,
+        };
+        match name {
+            Some(name) => write!(out, "&{name};")?,
+            None => write_numeric(c, options.style, out)?,
+        }
+    }
+    out.write_str(&s[start..])
+}
+
+/// 将字符写为数字编码（如 `&#27979;`、`&#x6D4B;`），在栈上拼接以避免格式化宏的开销。
+fn write_numeric<W: Write + ?Sized>(c: char, style: EncodeStyle, out: &mut W) -> fmt::Result {
+    let (radix, digits) = match style {
+        EncodeStyle::HexLower => (16, b"0123456789abcdef"),
+        EncodeStyle::HexUpper => (16, b"0123456789ABCDEF"),
+        EncodeStyle::Decimal | EncodeStyle::Named => (10, b"0123456789ABCDEF"),
+    };
+    // 最长为 `&#1114111;` 或 `&#x10FFFF;`。
+    let mut buf = [b';'; 10];
+    let mut i = buf.len() - 1;
+    let mut n = c as u32;
+    loop {
+        i -= 1;
+        buf[i] = digits[(n % radix) as usize];
+        n /= radix;
+        if n == 0 {
+            break;
+        }
+    }
+    if radix == 16 {
+        i -= 1;
+        buf[i] = b'x';
+    }
+    buf[i - 2..i].copy_from_slice(b"&#");
+    out.write_str(core::str::from_utf8(&buf[i - 2..]).unwrap_or_default())
 }
 
 /// 将字符串编码为 HTML 实体编码格式。
@@ -58,11 +383,50 @@ pub fn decode(u: &str) -> String {
 ///
 /// - 函数对每个字符进行编码，不管字符是否是 ASCII 还是非 ASCII 字符。
 /// - 编码结果是一个包含 HTML 实体编码的字符串，可以在 HTML 文档中直接使用。
+/// - 只需要编码部分字符（如 HTML 特殊字符或非 ASCII 字符），或需要十六进制、命名实体格式的输出时，请使用 [`EncodeOptions`]。
+/// - 需要将文本嵌入属性值、`<script>`、`<style>` 或 URL 时，请使用按上下文转义的 [`escape`]。
 pub fn encode(s: &str) -> String {
-    s.chars()
-        .map(|c| format!("&#{};", c as u32))
-        .collect::<Vec<String>>()
-        .join("")
+    encode_with(s, &EncodeOptions::new())
+}
+
+/// 将字符串编码为 HTML 实体编码后写入 `out`，不分配中间字符串。
+///
+/// # 说明
+///
+/// 编码规则与 [`encode`] 相同，只在写入 `out` 失败时返回错误，适合在循环中复用同一个缓冲区。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::ascii::encode_to;
+/// let mut out = String::new();
+/// for word in ["测", "试"] {
+///     encode_to(word, &mut out).unwrap();
+/// }
+/// assert_eq!(out, "&#27979;&#35797;");
+/// ```
+pub fn encode_to<W: Write + ?Sized>(s: &str, out: &mut W) -> fmt::Result {
+    encode_into(s, &EncodeOptions::new(), out)
+}
+
+/// 将字符串编码为 HTML 实体编码，没有需要编码的字符（即输入为空）时直接借用输入。
+///
+/// # 说明
+///
+/// 默认选项会编码所有字符，因此只有空字符串会被直接借用；配合 [`EncodeOptions::encode_cow`] 只编码部分字符时，常见的无需编码的输入不会分配内存。
+///
+/// # 示例
+///
+/// ```rust
+/// # use std::borrow::Cow;
+/// # use utils_rust::ascii::{encode_cow, EncodeMode, EncodeOptions};
+/// assert_eq!(encode_cow("测"), "&#27979;");
+///
+/// let options = EncodeOptions::new().mode(EncodeMode::HtmlSpecial);
+/// assert!(matches!(options.encode_cow("测试"), Cow::Borrowed("测试")));
+/// ```
+pub fn encode_cow(s: &str) -> Cow<'_, str> {
+    encode_cow_with(s, &EncodeOptions::new())
 }
 
 #[cfg(test)]
@@ -85,8 +449,248 @@ mod tests {
         assert_eq!(b, c)
     }
 
+    #[test]
+    fn test_encode_unrepresentable() {
+        // 每个字符都会被编码，但 `&#0;` 和部分 `&#128;`～`&#159;` 按 WHATWG 规则解码为其他字符。
+        assert_eq!(encode("\0a"), "&#0;&#97;");
+        assert_eq!(encode("\u{80}\u{81}\u{9F}"), "&#128;&#129;&#159;");
+        assert_eq!(
+            decode(&encode("\0\u{80}\u{8D}\u{9F}")),
+            "\u{FFFD}\u{20AC}\u{8D}\u{178}"
+        );
+    }
+
+    #[test]
+    fn test_decode_hex() {
+        assert_eq!(decode("&#x6d4b;&#x8bd5;"), "测试");
+        assert_eq!(decode("&#X6D4B;&#X8BD5;"), "测试");
+        assert_eq!(decode("&#x6D4B;&#35797;"), "测试");
+        assert_eq!(decode("&#27979;&#X8bD5;"), "测试");
+    }
+
+    #[test]
+    fn test_decode_named() {
+        assert_eq!(decode("&amp;&lt;&gt;&quot;&apos;"), "&<>\"'");
+        assert_eq!(decode("&nbsp;&eacute;&hellip;"), "\u{a0}é…");
+        assert_eq!(decode("&NotEqualTilde;"), "\u{2242}\u{338}");
+        assert_eq!(decode("&amp&copy&notit;"), "&©¬it;");
+        assert_eq!(decode("&hellip&unknown;"), "&hellip&unknown;");
+    }
+
+    #[test]
+    fn test_decode_plain_text() {
+        assert_eq!(decode("Hello &#27979; world"), "Hello 测 world");
+        assert_eq!(decode("a &amp; b &lt; c"), "a & b < c");
+        assert_eq!(decode("no references"), "no references");
+        assert_eq!(decode("& &# &#x &#; & ; &foo;"), "& &# &#x &#; & ; &foo;");
+        assert_eq!(decode("测&#35797;！"), "测试！");
+        assert_eq!(decode("&#99999999;x"), "\u{FFFD}x");
+    }
+
+    #[test]
+    fn test_decode_missing_semicolon() {
+        assert_eq!(decode("&#27979 &#x8BD5"), "测 试");
+        assert_eq!(decode("&#65x&#x41g"), "AxAg");
+        assert_eq!(decode("&#0&#xD800"), "\u{FFFD}\u{FFFD}");
+        assert_eq!(decode("&#128"), "€");
+        assert_eq!(
+            DecodeOptions::new()
+                .policy(ErrorPolicy::Passthrough)
+                .decode("&#0 &#65")
+                .unwrap(),
+            "&#0 A"
+        );
+    }
+
+    #[test]
+    fn test_decode_attribute() {
+        let text = DecodeOptions::new();
+        let attribute = DecodeOptions::new().attribute(true);
+        let cases = [
+            ("&notit;", "¬it;", "&notit;"),
+            ("&not=", "¬=", "&not="),
+            ("&not ", "¬ ", "¬ "),
+            ("&not;it", "¬it", "¬it"),
+            ("&copy9", "©9", "&copy9"),
+            ("&ampx;", "&x;", "&ampx;"),
+            ("?a=1&lt=2", "?a=1<=2", "?a=1&lt=2"),
+            ("&#65x", "Ax", "Ax"),
+            ("&amp", "&", "&"),
+        ];
+        for (input, as_text, as_attribute) in cases {
+            assert_eq!(text.decode(input).unwrap(), as_text, "{input}");
+            assert_eq!(attribute.decode(input).unwrap(), as_attribute, "{input}");
+        }
+
+        // 属性值中按原文保留的实体不视为错误。
+        let strict = attribute.policy(ErrorPolicy::Strict);
+        assert_eq!(strict.decode("&copy=").unwrap(), "&copy=");
+        assert!(strict.decode("&copy ").is_err());
+    }
+
+    #[test]
+    fn test_decode_policy() {
+        let input = "a&#1114112;b&#xDFFF;c &#; &amp";
+        let decode = |policy| DecodeOptions::new().policy(policy).decode(input);
+        assert_eq!(decode(ErrorPolicy::Drop).unwrap(), "abc &#; &");
+        assert_eq!(
+            decode(ErrorPolicy::Replace).unwrap(),
+            "a\u{FFFD}b\u{FFFD}c &#; &"
+        );
+        assert_eq!(
+            decode(ErrorPolicy::Passthrough).unwrap(),
+            "a&#1114112;b&#xDFFF;c &#; &"
+        );
+        assert_eq!(decode(ErrorPolicy::Strict).unwrap_err().offset, 1);
+        assert_eq!(
+            DecodeOptions::default().decode(input).unwrap(),
+            super::decode(input)
+        );
+    }
+
+    #[test]
+    fn test_decode_numeric_remap() {
+        assert_eq!(decode("&#128;&#x9f;&#150;"), "€Ÿ–");
+        assert_eq!(decode("&#129;&#x8D;"), "\u{81}\u{8D}");
+        assert_eq!(decode("&#0;&#x0000;"), "\u{FFFD}\u{FFFD}");
+        assert_eq!(decode("&#xD800;&#57343;"), "\u{FFFD}\u{FFFD}");
+        assert_eq!(decode("&#x110000;&#xFFFFFFFFFFFF;"), "\u{FFFD}\u{FFFD}");
+        assert_eq!(decode("&#127;&#160;&#x10FFFF;"), "\u{7F}\u{A0}\u{10FFFF}");
+        assert_eq!(
+            try_decode("&#0;").unwrap_err().kind,
+            DecodeErrorKind::NullCharacter
+        );
+    }
+
+    #[test]
+    fn test_encode_mode() {
+        let input = "a <b> & \"c\" 'd' 测试 👍";
+        let encode = |mode| EncodeOptions::new().mode(mode).encode(input);
+        assert_eq!(encode(EncodeMode::All), super::encode(input));
+        assert_eq!(
+            encode(EncodeMode::HtmlSpecial),
+            "a &#60;b&#62; &#38; &#34;c&#34; &#39;d&#39; 测试 👍"
+        );
+        assert_eq!(
+            encode(EncodeMode::NonAscii),
+            "a <b> & \"c\" 'd' &#27979;&#35797; &#128077;"
+        );
+        assert_eq!(
+            encode(EncodeMode::Except("abcd 测")),
+            "a &#60;b&#62; &#38; &#34;c&#34; &#39;d&#39; 测&#35797; &#128077;"
+        );
+        assert_eq!(
+            EncodeOptions::new()
+                .mode(EncodeMode::Except(""))
+                .encode("ab"),
+            "&#97;&#98;"
+        );
+    }
+
+    #[test]
+    fn test_encode_style() {
+        let input = "<测> & ©\t";
+        let encode = |style| EncodeOptions::new().style(style).encode(input);
+        assert_eq!(encode(EncodeStyle::Decimal), super::encode(input));
+        assert_eq!(
+            encode(EncodeStyle::HexLower),
+            "&#x3c;&#x6d4b;&#x3e;&#x20;&#x26;&#x20;&#xa9;&#x9;"
+        );
+        assert_eq!(
+            encode(EncodeStyle::HexUpper),
+            "&#x3C;&#x6D4B;&#x3E;&#x20;&#x26;&#x20;&#xA9;&#x9;"
+        );
+        assert_eq!(
+            encode(EncodeStyle::Named),
+            "&lt;&#27979;&gt;&#32;&amp;&#32;&copy;&Tab;"
+        );
+        for style in [
+            EncodeStyle::Decimal,
+            EncodeStyle::HexLower,
+            EncodeStyle::HexUpper,
+            EncodeStyle::Named,
+        ] {
+            assert_eq!(decode(&encode(style)), input);
+        }
+    }
+
+    #[test]
+    fn test_write_to() {
+        let mut out = String::from("> ");
+        encode_to("测<", &mut out).unwrap();
+        decode_to(" &amp;&#27979;", &mut out).unwrap();
+        assert_eq!(out, "> &#27979;&#60; &测");
+
+        let options = EncodeOptions::new().mode(EncodeMode::HtmlSpecial);
+        let mut out = String::new();
+        options.encode_to("a<b>c", &mut out).unwrap();
+        assert_eq!(out, "a&#60;b&#62;c");
+    }
+
+    #[test]
+    fn test_cow() {
+        assert!(matches!(decode_cow("测试"), Cow::Borrowed("测试")));
+        assert!(matches!(decode_cow("&#27979;"), Cow::Owned(_)));
+        assert_eq!(decode_cow("a &amp; b"), "a & b");
+        assert!(matches!(encode_cow(""), Cow::Borrowed("")));
+        assert_eq!(encode_cow("测"), "&#27979;");
+
+        let options = EncodeOptions::new().mode(EncodeMode::NonAscii);
+        assert!(matches!(
+            options.encode_cow("ascii"),
+            Cow::Borrowed("ascii")
+        ));
+        assert_eq!(options.encode_cow("a测"), "a&#27979;");
+
+        let options = DecodeOptions::new().policy(ErrorPolicy::Strict);
+        assert!(matches!(
+            options.decode_cow("plain"),
+            Ok(Cow::Borrowed("plain"))
+        ));
+        assert!(options.decode_cow("&#0;").is_err());
+    }
+
     #[test]
     fn test_parse_unicode() {
         assert_eq!(parse_unicode("128077"), Ok('👍'));
+        assert_eq!(parse_unicode("x1F44D"), Ok('👍'));
+        assert_eq!(parse_unicode("X1f44d"), Ok('👍'));
+        assert_eq!(parse_unicode("x"), Err(DecodeErrorKind::InvalidDigits));
+        assert_eq!(parse_unicode("xD800"), Err(DecodeErrorKind::Surrogate));
+        assert_eq!(parse_unicode("1114112"), Err(DecodeErrorKind::OutOfRange));
+        assert_eq!(
+            parse_unicode("99999999999"),
+            Err(DecodeErrorKind::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_try_decode() {
+        assert_eq!(
+            try_decode("Hello &#27979; &amp; &x").unwrap(),
+            "Hello 测 & &x"
+        );
+
+        let cases = [
+            ("ab&#;", 2, "&#", DecodeErrorKind::InvalidDigits),
+            ("&#xg;", 0, "&#x", DecodeErrorKind::InvalidDigits),
+            ("测&#1114112;", 3, "&#1114112;", DecodeErrorKind::OutOfRange),
+            ("&#55296;", 0, "&#55296;", DecodeErrorKind::Surrogate),
+            ("&#27979 ", 0, "&#27979", DecodeErrorKind::MissingSemicolon),
+            ("&#0", 0, "&#0", DecodeErrorKind::NullCharacter),
+            ("AT&T; &foo", 2, "&T;", DecodeErrorKind::UnknownName),
+            (
+                "&lt;&copy 2024",
+                4,
+                "&copy",
+                DecodeErrorKind::MissingSemicolon,
+            ),
+        ];
+        for (input, offset, reference, kind) in cases {
+            let err = try_decode(input).unwrap_err();
+            assert_eq!(err.offset, offset, "{input}");
+            assert_eq!(err.reference, reference, "{input}");
+            assert_eq!(err.kind, kind, "{input}");
+        }
     }
 }
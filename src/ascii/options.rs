@@ -0,0 +1,246 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::fmt::{self, Write};
+use core::str::Utf8Error;
+
+use crate::codec::{Decoder, Encoder, ErrorPolicy};
+
+use super::bytes::{decode_bytes_with, encode_bytes_with, Utf8Policy};
+use super::{
+    decode_cow_with, decode_with, encode_cow_with, encode_into, encode_with, scan, DecodeError,
+};
+
+/// HTML 实体编码的解码选项。
+///
+/// # 说明
+///
+/// 默认选项与 [`decode`](super::decode) 的行为一致：无法解析的字符引用被替换为 U+FFFD。可以通过 [`DecodeOptions::policy`] 选择其他处理方式。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::ascii::{DecodeOptions, ErrorPolicy};
+/// let input = "&#27979;&#xD800;";
+/// let decode = |policy| DecodeOptions::new().policy(policy).decode(input);
+/// assert_eq!(decode(ErrorPolicy::Drop).unwrap(), "测");
+/// assert_eq!(decode(ErrorPolicy::Replace).unwrap(), "测\u{FFFD}");
+/// assert_eq!(decode(ErrorPolicy::Passthrough).unwrap(), "测&#xD800;");
+/// assert!(decode(ErrorPolicy::Strict).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecodeOptions {
+    pub(crate) policy: ErrorPolicy,
+    pub(crate) utf8: Utf8Policy,
+    pub(crate) attribute: bool,
+}
+
+impl DecodeOptions {
+    /// 创建默认的解码选项。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置无法解析的字符引用的处理方式，默认为 [`ErrorPolicy::Replace`]：
+    ///
+    /// - [`ErrorPolicy::Strict`]：返回 [`DecodeError`]；缺少有效数字、缺少结尾 `;` 的字符引用以及以 `;` 结尾的未知实体名称同样视为错误。
+    /// - [`ErrorPolicy::Replace`]：码点为 `0`、超出范围或为代理项的数字引用替换为 U+FFFD（`�`），与浏览器的行为一致。
+    /// - [`ErrorPolicy::Passthrough`]：保留这些数字引用的原文，如 `&#xD800;`。
+    /// - [`ErrorPolicy::Drop`]：删除这些数字引用。
+    ///
+    /// 非严格模式下，缺少结尾 `;` 的字符引用照常解码，不构成字符引用的 `&`（如 `&foo;`、`&#;`）按普通文本保留。
+    pub fn policy(mut self, policy: ErrorPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// 设置字节输入中非法 UTF-8 序列的处理方式，仅影响 [`DecodeOptions::decode_bytes`]。
+    pub fn utf8(mut self, utf8: Utf8Policy) -> Self {
+        self.utf8 = utf8;
+        self
+    }
+
+    /// 设置是否按属性值的规则解码，默认按文本内容解码。
+    ///
+    /// 与浏览器解析属性值的规则一致：缺少 `;` 的旧式实体后紧跟 `=` 或字母数字时不解码，以免破坏 URL 中的查询参数。这种情况不视为错误，严格模式下同样按原文保留。
+    ///
+    /// ```rust
+    /// # use utils_rust::ascii::DecodeOptions;
+    /// let text = DecodeOptions::new();
+    /// let attribute = DecodeOptions::new().attribute(true);
+    /// assert_eq!(text.decode("?a=1&copy=2").unwrap(), "?a=1©=2");
+    /// assert_eq!(attribute.decode("?a=1&copy=2").unwrap(), "?a=1&copy=2");
+    /// assert_eq!(attribute.decode("&notit; &not &copy;=").unwrap(), "&notit; ¬ ©=");
+    /// ```
+    pub fn attribute(mut self, attribute: bool) -> Self {
+        self.attribute = attribute;
+        self
+    }
+
+    /// 按当前选项解码字符串，仅在 [`ErrorPolicy::Strict`] 下可能返回错误。
+    pub fn decode(&self, u: &str) -> Result<String, DecodeError> {
+        decode_with(u, self)
+    }
+
+    /// 按当前选项解码字符串，输入中没有任何字符引用时直接借用输入。
+    pub fn decode_cow<'u>(&self, u: &'u str) -> Result<Cow<'u, str>, DecodeError> {
+        decode_cow_with(u, self)
+    }
+
+    /// 按当前选项解码字节输入，返回 UTF-8 字节；输入没有变化时直接借用输入。
+    ///
+    /// 在 [`Utf8Policy::Strict`] 下，非法的 UTF-8 序列报告为 [`DecodeErrorKind::InvalidUtf8`](super::DecodeErrorKind::InvalidUtf8)。
+    pub fn decode_bytes<'b>(&self, bytes: &'b [u8]) -> Result<Cow<'b, [u8]>, DecodeError> {
+        decode_bytes_with(bytes, self)
+    }
+}
+
+/// 编码时选择需要转换为实体编码的字符。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EncodeMode<'a> {
+    /// 编码所有字符。
+    #[default]
+    All,
+    /// 仅编码 HTML 特殊字符 `<`、`>`、`&`、`"`、`'`。
+    HtmlSpecial,
+    /// 仅编码非 ASCII 字符。
+    NonAscii,
+    /// 仅编码不在给定字符集合中的字符。
+    Except(&'a str),
+}
+
+impl EncodeMode<'_> {
+    /// 判断字符在当前模式下是否需要编码。
+    pub(crate) fn escapes(&self, c: char) -> bool {
+        match self {
+            EncodeMode::All => true,
+            EncodeMode::HtmlSpecial => matches!(c, '<' | '>' | '&' | '"' | '\''),
+            EncodeMode::NonAscii => !c.is_ascii(),
+            EncodeMode::Except(keep) => !keep.contains(c),
+        }
+    }
+
+    /// 查找第一个需要编码的字符的位置，HTML 特殊字符和非 ASCII 模式下按机器字批量扫描。
+    pub(crate) fn find(&self, s: &str) -> Option<usize> {
+        match self {
+            EncodeMode::All => (!s.is_empty()).then_some(0),
+            EncodeMode::HtmlSpecial => scan::find_html_special(s.as_bytes()),
+            EncodeMode::NonAscii => scan::find_non_ascii(s.as_bytes()),
+            EncodeMode::Except(_) => s
+                .char_indices()
+                .find(|&(_, c)| self.escapes(c))
+                .map(|(i, _)| i),
+        }
+    }
+}
+
+/// 编码时输出的实体编码格式。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EncodeStyle {
+    /// 十进制数字编码，如 `&#27979;`。
+    #[default]
+    Decimal,
+    /// 小写十六进制数字编码，如 `&#x6d4b;`。
+    HexLower,
+    /// 大写十六进制数字编码，如 `&#x6D4B;`。
+    HexUpper,
+    /// 优先使用命名实体（如 `&amp;`、`&copy;`），没有对应名称的字符使用十进制数字编码。
+    Named,
+}
+
+/// HTML 实体编码的编码选项。
+///
+/// # 说明
+///
+/// 默认选项与 [`encode`](super::encode) 的行为一致：每个字符都会被编码为十进制数字编码。可以通过 [`EncodeOptions::mode`] 只编码部分字符，以减小输出的体积；通过 [`EncodeOptions::style`] 选择输出格式。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::ascii::{EncodeMode, EncodeOptions, EncodeStyle};
+/// let input = "<a>测试</a>";
+/// let encode = |mode| EncodeOptions::new().mode(mode).encode(input);
+/// assert_eq!(encode(EncodeMode::HtmlSpecial), "&#60;a&#62;测试&#60;/a&#62;");
+/// assert_eq!(encode(EncodeMode::NonAscii), "<a>&#27979;&#35797;</a>");
+/// assert_eq!(encode(EncodeMode::Except("<>/a")), "<a>&#27979;&#35797;</a>");
+///
+/// let options = EncodeOptions::new().mode(EncodeMode::NonAscii);
+/// assert_eq!(options.style(EncodeStyle::HexUpper).encode("测试"), "&#x6D4B;&#x8BD5;");
+/// assert_eq!(options.style(EncodeStyle::Named).encode("© 测"), "&copy; &#27979;");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EncodeOptions<'a> {
+    pub(crate) mode: EncodeMode<'a>,
+    pub(crate) style: EncodeStyle,
+    pub(crate) utf8: Utf8Policy,
+}
+
+impl<'a> EncodeOptions<'a> {
+    /// 创建默认的编码选项。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置需要编码的字符范围。
+    pub fn mode(mut self, mode: EncodeMode<'a>) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// 设置输出的实体编码格式。
+    pub fn style(mut self, style: EncodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// 设置字节输入中非法 UTF-8 序列的处理方式，仅影响 [`EncodeOptions::encode_bytes`]。
+    pub fn utf8(mut self, utf8: Utf8Policy) -> Self {
+        self.utf8 = utf8;
+        self
+    }
+
+    /// 按当前选项编码字符串。
+    pub fn encode(&self, s: &str) -> String {
+        encode_with(s, self)
+    }
+
+    /// 按当前选项编码字符串并写入 `out`，不分配中间字符串。
+    pub fn encode_to<W: Write + ?Sized>(&self, s: &str, out: &mut W) -> fmt::Result {
+        encode_into(s, self, out)
+    }
+
+    /// 按当前选项编码字符串，没有需要编码的字符时直接借用输入。
+    pub fn encode_cow<'s>(&self, s: &'s str) -> Cow<'s, str> {
+        encode_cow_with(s, self)
+    }
+
+    /// 按当前选项编码字节输入；没有需要编码的字符时直接借用输入。
+    ///
+    /// 仅在 [`Utf8Policy::Strict`] 下遇到非法的 UTF-8 序列时返回错误。
+    pub fn encode_bytes<'b>(&self, bytes: &'b [u8]) -> Result<Cow<'b, [u8]>, Utf8Error> {
+        encode_bytes_with(bytes, self)
+    }
+}
+
+impl Decoder for DecodeOptions {
+    type Error = DecodeError;
+
+    fn decode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, DecodeError> {
+        decode_cow_with(input, self)
+    }
+
+    fn decode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, DecodeError> {
+        decode_bytes_with(input, self)
+    }
+}
+
+impl Encoder for EncodeOptions<'_> {
+    type Error = Utf8Error;
+
+    fn encode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, Utf8Error> {
+        Ok(encode_cow_with(input, self))
+    }
+
+    fn encode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, Utf8Error> {
+        encode_bytes_with(input, self)
+    }
+}
@@ -0,0 +1,77 @@
+//! 按机器字（SWAR）批量扫描字节的快速路径，用于跳过无需处理的文本。
+//!
+//! 解码时查找 `&` 直接使用 `str::find`，标准库内部已按机器字实现了 `memchr`。
+
+use core::mem::size_of;
+
+const WORD: usize = size_of::<usize>();
+const LO: usize = usize::from_ne_bytes([0x01; WORD]);
+const HI: usize = usize::from_ne_bytes([0x80; WORD]);
+
+/// 判断机器字中是否存在值为 `0` 的字节。
+#[inline]
+fn has_zero(x: usize) -> bool {
+    x.wrapping_sub(LO) & !x & HI != 0
+}
+
+/// 按机器字跳过不满足 `hit` 的部分，返回需要逐字节检查的起始位置。
+#[inline]
+fn skip_words(bytes: &[u8], hit: impl Fn(usize) -> bool) -> usize {
+    bytes
+        .chunks_exact(WORD)
+        .take_while(|chunk| !hit(usize::from_ne_bytes((*chunk).try_into().unwrap())))
+        .count()
+        * WORD
+}
+
+/// 查找第一个非 ASCII 字节的位置。
+pub(crate) fn find_non_ascii(bytes: &[u8]) -> Option<usize> {
+    let start = skip_words(bytes, |word| word & HI != 0);
+    bytes[start..]
+        .iter()
+        .position(|b| !b.is_ascii())
+        .map(|i| start + i)
+}
+
+/// 查找第一个属于 `needles` 的字节的位置。
+pub(crate) fn find_any<const N: usize>(bytes: &[u8], needles: [u8; N]) -> Option<usize> {
+    let start = skip_words(bytes, |word| {
+        needles.iter().any(|&b| has_zero(word ^ (LO * b as usize)))
+    });
+    bytes[start..]
+        .iter()
+        .position(|b| needles.contains(b))
+        .map(|i| start + i)
+}
+
+/// 查找第一个 HTML 特殊字符 `<`、`>`、`&`、`"`、`'` 的位置。
+pub(crate) fn find_html_special(bytes: &[u8]) -> Option<usize> {
+    find_any(bytes, *b"<>&\"'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_non_ascii() {
+        let text = "The quick brown fox jumps over the lazy dog 测试".as_bytes();
+        for start in 0..text.len() {
+            let expected = text[start..].iter().position(|b| !b.is_ascii());
+            assert_eq!(find_non_ascii(&text[start..]), expected);
+        }
+        assert_eq!(find_non_ascii(b""), None);
+        assert_eq!(find_non_ascii(&[b'a'; 100]), None);
+    }
+
+    #[test]
+    fn test_find_html_special() {
+        let text = b"0123456789abcdef<0123456789abcdef>\"'&0123456789";
+        for start in 0..text.len() {
+            let expected = text[start..].iter().position(|b| b"<>&\"'".contains(b));
+            assert_eq!(find_html_special(&text[start..]), expected);
+        }
+        assert_eq!(find_html_special("测试 plain text".as_bytes()), None);
+        assert_eq!(find_any(b"\x00\x7f\x80\xff", [0x80]), Some(2));
+    }
+}
@@ -0,0 +1,194 @@
+//! 配合 `#[serde(with = "...")]` 使用的序列化适配器：序列化时编码，反序列化时解码。
+//!
+//! # 说明
+//!
+//! 适用于 JSON 等格式中以 HTML 实体编码存储的字符串字段。序列化使用 [`encode`](super::encode) 的规则，反序列化使用 [`decode`](super::decode) 的规则；字段类型为 [`Option<String>`] 时使用 [`option`] 子模块。
+//!
+//! # 示例
+//!
+//! ```rust
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(Serialize, Deserialize)]
+//! struct User {
+//!     #[serde(with = "utils_rust::ascii::serde")]
+//!     name: String,
+//! }
+//!
+//! let user: User = serde_json::from_str(r#"{"name":"&#27979;&#35797;"}"#).unwrap();
+//! assert_eq!(user.name, "测试");
+//! assert_eq!(serde_json::to_string(&user).unwrap(), r#"{"name":"&#27979;&#35797;"}"#);
+//! ```
+
+use alloc::string::String;
+use core::fmt;
+
+use ::serde::de::{self, Deserializer, Visitor};
+use ::serde::{Serialize, Serializer};
+
+use super::{decode, encode_to};
+
+/// 以编码后的形式格式化文本，避免序列化时分配中间字符串。
+struct Encoded<'a>(&'a str);
+
+impl fmt::Display for Encoded<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        encode_to(self.0, f)
+    }
+}
+
+impl Serialize for Encoded<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// 将字符串解码后返回的访问器。
+struct DecodeVisitor;
+
+impl Visitor<'_> for DecodeVisitor {
+    type Value = String;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an HTML entity encoded string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<String, E> {
+        Ok(decode(v))
+    }
+}
+
+/// 将字段编码为 HTML 实体编码格式后序列化。
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: AsRef<str> + ?Sized,
+    S: Serializer,
+{
+    Encoded(value.as_ref()).serialize(serializer)
+}
+
+/// 反序列化字符串并解码其中的字符引用。
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    deserializer.deserialize_str(DecodeVisitor)
+}
+
+/// 用于 [`Option<String>`] 字段的适配器，`None` 按原样序列化。
+///
+/// # 示例
+///
+/// ```rust
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Serialize, Deserialize)]
+/// struct User {
+///     #[serde(with = "utils_rust::ascii::serde::option", default)]
+///     nickname: Option<String>,
+/// }
+///
+/// let user: User = serde_json::from_str(r#"{"nickname":"&lt;测&gt;"}"#).unwrap();
+/// assert_eq!(user.nickname.as_deref(), Some("<测>"));
+/// let user: User = serde_json::from_str("{}").unwrap();
+/// assert_eq!(user.nickname, None);
+/// ```
+pub mod option {
+    use alloc::string::String;
+    use core::fmt;
+
+    use ::serde::de::{Deserializer, Visitor};
+    use ::serde::Serializer;
+
+    use super::Encoded;
+
+    /// 将 `Some` 中的字段编码后序列化。
+    pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<str>,
+        S: Serializer,
+    {
+        match value {
+            Some(value) => serializer.serialize_some(&Encoded(value.as_ref())),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// 将 `null` 反序列化为 `None`，其余按字符串解码。
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<String>, D::Error> {
+        deserializer.deserialize_option(OptionVisitor)
+    }
+
+    struct OptionVisitor;
+
+    impl<'de> Visitor<'de> for OptionVisitor {
+        type Value = Option<String>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("an optional HTML entity encoded string")
+        }
+
+        fn visit_none<E>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D: Deserializer<'de>>(self, d: D) -> Result<Self::Value, D::Error> {
+            super::deserialize(d).map(Some)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct User {
+        #[serde(with = "crate::ascii::serde")]
+        name: String,
+        #[serde(with = "crate::ascii::serde::option", default)]
+        nickname: Option<String>,
+    }
+
+    #[test]
+    fn test_serialize() {
+        let user = User {
+            name: "测试".to_string(),
+            nickname: Some("<a>".to_string()),
+        };
+        assert_eq!(
+            serde_json::to_string(&user).unwrap(),
+            r#"{"name":"&#27979;&#35797;","nickname":"&#60;&#97;&#62;"}"#
+        );
+        let user = User {
+            name: String::new(),
+            nickname: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&user).unwrap(),
+            r#"{"name":"","nickname":null}"#
+        );
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let user: User =
+            serde_json::from_str(r#"{"name":"&#27979;&#x8BD5; &amp; &lt;","nickname":null}"#)
+                .unwrap();
+        assert_eq!(user.name, "测试 & <");
+        assert_eq!(user.nickname, None);
+
+        let user: User = serde_json::from_str(r#"{"name":"&#0;","nickname":"&copy;"}"#).unwrap();
+        assert_eq!(user.name, "\u{FFFD}");
+        assert_eq!(user.nickname.as_deref(), Some("©"));
+
+        assert!(serde_json::from_str::<User>(r#"{"name":1}"#).is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let user = User {
+            name: "<p>\"测试\" & 'ok'</p>".to_string(),
+            nickname: Some("😀".to_string()),
+        };
+        let json = serde_json::to_string(&user).unwrap();
+        assert_eq!(serde_json::from_str::<User>(&json).unwrap(), user);
+    }
+}
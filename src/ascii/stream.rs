@@ -0,0 +1,346 @@
+use std::io::{self, ErrorKind, Read, Write};
+use std::mem;
+use std::str;
+
+use super::entities::MAX_NAME_LEN;
+use super::{decode_string, encode_into, DecodeOptions, EncodeOptions};
+
+/// 每次从底层读取器读取的字节数。
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// 暂缓解码的字符引用的最大字节数：`&` 加上最长的实体名称（不含 `;`），数字引用在此长度内可以容纳 `&#x` 之后的 29 位数字。
+const MAX_PENDING: usize = MAX_NAME_LEN;
+
+fn invalid_utf8() -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, "输入不是有效的 UTF-8")
+}
+
+/// 返回 `bytes` 中可以解码的 UTF-8 前缀；末尾被截断的字符不计入，遇到非法序列时返回错误。
+fn utf8_prefix(bytes: &[u8]) -> io::Result<&str> {
+    match str::from_utf8(bytes) {
+        Ok(s) => Ok(s),
+        Err(e) if e.error_len().is_none() => Ok(str::from_utf8(&bytes[..e.valid_up_to()]).unwrap()),
+        Err(_) => Err(invalid_utf8()),
+    }
+}
+
+/// 返回 `text` 中可以安全解码的前缀长度，末尾可能被截断的字符引用（如 `&#279`、`&am`）留待读取更多输入后再解码。
+///
+/// 只检查末尾 [`MAX_PENDING`] 字节：更早出现的 `&` 之后已有超过最长实体名称的字母数字，不再暂缓，使缓冲区保持有界。
+fn complete_len(text: &str) -> usize {
+    let tail = text.len().saturating_sub(MAX_PENDING);
+    let Some(i) = text.as_bytes()[tail..].iter().rposition(|&b| b == b'&') else {
+        return text.len();
+    };
+    let i = tail + i;
+    let open = text[i + 1..]
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || b == b'#');
+    if open {
+        i
+    } else {
+        text.len()
+    }
+}
+
+/// 将写入的 UTF-8 文本编码为 HTML 实体编码，并写入底层的 [`Write`]。
+///
+/// # 说明
+///
+/// 写入的字节按块处理，被块边界截断的 UTF-8 字符会暂存到下一次写入时再编码，因此可以用任意大小的缓冲区处理任意大的输入。写入完毕后应调用 [`EntityEncoder::finish`]，检查输入是否以完整的字符结尾并取回底层的写入器。
+///
+/// # 示例
+///
+/// ```rust
+/// # use std::io::Write;
+/// # use utils_rust::ascii::EntityEncoder;
+/// let mut encoder = EntityEncoder::new(Vec::new());
+/// let bytes = "测试".as_bytes();
+/// encoder.write_all(&bytes[..4]).unwrap();
+/// encoder.write_all(&bytes[4..]).unwrap();
+/// assert_eq!(encoder.finish().unwrap(), b"&#27979;&#35797;");
+/// ```
+#[derive(Debug)]
+pub struct EntityEncoder<'a, W: Write> {
+    inner: W,
+    options: EncodeOptions<'a>,
+    /// 上一次写入末尾被截断的 UTF-8 字节。
+    pending: Vec<u8>,
+    /// 复用的编码输出缓冲区。
+    buf: String,
+}
+
+impl<W: Write> EntityEncoder<'static, W> {
+    /// 使用默认编码选项创建编码器。
+    pub fn new(inner: W) -> Self {
+        Self::with_options(inner, EncodeOptions::new())
+    }
+}
+
+impl<'a, W: Write> EntityEncoder<'a, W> {
+    /// 使用指定的编码选项创建编码器。
+    pub fn with_options(inner: W, options: EncodeOptions<'a>) -> Self {
+        Self {
+            inner,
+            options,
+            pending: Vec::with_capacity(4),
+            buf: String::new(),
+        }
+    }
+
+    /// 获取底层写入器的引用。
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// 结束编码并返回底层写入器；输入以不完整的 UTF-8 字符结尾时返回错误。
+    pub fn finish(mut self) -> io::Result<W> {
+        if !self.pending.is_empty() {
+            return Err(invalid_utf8());
+        }
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    fn encode(&mut self, s: &str) -> io::Result<()> {
+        self.buf.clear();
+        let _ = encode_into(s, &self.options, &mut self.buf);
+        self.inner.write_all(self.buf.as_bytes())
+    }
+}
+
+impl<W: Write> Write for EntityEncoder<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut input = buf;
+        if !self.pending.is_empty() {
+            // 先补全上一次被截断的字符。
+            let before = self.pending.len();
+            let take = input.len().min(4 - before);
+            self.pending.extend_from_slice(&input[..take]);
+            let mut pending = mem::take(&mut self.pending);
+            let s = utf8_prefix(&pending)?;
+            if s.is_empty() {
+                self.pending = pending;
+                return Ok(buf.len());
+            }
+            let used = s.len() - before;
+            self.encode(s)?;
+            pending.clear();
+            self.pending = pending;
+            input = &input[used..];
+        }
+        let s = utf8_prefix(input)?;
+        self.encode(s)?;
+        self.pending.extend_from_slice(&input[s.len()..]);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// 从底层的 [`Read`] 中读取 HTML 实体编码的文本，并以解码后的 UTF-8 字节提供读取。
+///
+/// # 说明
+///
+/// 输入按块读取和解码，被块边界截断的 UTF-8 字符和字符引用（如 `&#279` 与 `79;`）会保留到读取更多输入后再解码，因此解码结果与对完整输入调用 [`DecodeOptions::decode`] 相同。
+///
+/// 为使内存占用有界，暂缓解码的字符引用最多为 `&` 加上最长的实体名称的长度（32 字节），即使输入中有很长的 `&aaaa…` 也不会无限缓冲。更长的字符引用只可能是无效的名称或补了很多零的数字引用，会按暂缓上限处截断后的前缀解码，结果可能与对完整输入解码不同。
+///
+/// 输入不是有效的 UTF-8，或在 [`ErrorPolicy::Strict`](super::ErrorPolicy::Strict) 下遇到不合规的字符引用时，读取返回 [`ErrorKind::InvalidData`] 错误；后者包装了 [`DecodeError`](super::DecodeError)，其偏移相对于整个输入。
+///
+/// # 示例
+///
+/// ```rust
+/// # use std::io::Read;
+/// # use utils_rust::ascii::EntityDecoder;
+/// let mut decoder = EntityDecoder::new("&#27979;&#35797; &amp; more".as_bytes());
+/// let mut out = String::new();
+/// decoder.read_to_string(&mut out).unwrap();
+/// assert_eq!(out, "测试 & more");
+/// ```
+#[derive(Debug)]
+pub struct EntityDecoder<R: Read> {
+    inner: R,
+    options: DecodeOptions,
+    /// 已读取但尚未解码的字节。
+    input: Vec<u8>,
+    /// 已解码但尚未被读取的文本。
+    output: String,
+    /// `output` 中已被读取的字节数。
+    pos: usize,
+    /// 已解码的输入字节数，用于修正错误偏移。
+    consumed: usize,
+    eof: bool,
+}
+
+impl<R: Read> EntityDecoder<R> {
+    /// 使用默认解码选项创建解码器。
+    pub fn new(inner: R) -> Self {
+        Self::with_options(inner, DecodeOptions::new())
+    }
+
+    /// 使用指定的解码选项创建解码器。
+    pub fn with_options(inner: R, options: DecodeOptions) -> Self {
+        Self {
+            inner,
+            options,
+            input: Vec::new(),
+            output: String::new(),
+            pos: 0,
+            consumed: 0,
+            eof: false,
+        }
+    }
+
+    /// 获取底层读取器的引用。
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// 返回底层读取器，已读取但尚未解码的数据将被丢弃。
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// 读取下一块输入，并将其中可以安全解码的部分解码到 `output`。
+    fn fill(&mut self) -> io::Result<()> {
+        if !self.eof {
+            let start = self.input.len();
+            self.input.resize(start + CHUNK_SIZE, 0);
+            let read = loop {
+                match self.inner.read(&mut self.input[start..]) {
+                    Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                    read => break read,
+                }
+            };
+            let n = read.inspect_err(|_| self.input.truncate(start))?;
+            self.input.truncate(start + n);
+            self.eof = n == 0;
+        }
+
+        let (text, end) = if self.eof {
+            let text = str::from_utf8(&self.input).map_err(|_| invalid_utf8())?;
+            (text, text.len())
+        } else {
+            let text = utf8_prefix(&self.input)?;
+            (text, complete_len(text))
+        };
+        self.output.clear();
+        self.pos = 0;
+        decode_string(&text[..end], &self.options, &mut self.output).map_err(|mut e| {
+            e.offset += self.consumed;
+            io::Error::new(ErrorKind::InvalidData, e)
+        })?;
+        self.consumed += end;
+        self.input.drain(..end);
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for EntityDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.output.len() {
+            if self.eof && self.input.is_empty() {
+                return Ok(0);
+            }
+            self.fill()?;
+        }
+        let n = (&self.output.as_bytes()[self.pos..]).read(buf)?;
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ascii::{decode, encode, DecodeError, DecodeErrorKind, ErrorPolicy};
+
+    /// 每次只返回一个字节的读取器，用于覆盖所有可能的块边界。
+    struct OneByte<'a>(&'a [u8]);
+
+    impl Read for OneByte<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.0.len().min(buf.len()).min(1);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    const INPUT: &str = "Hello &#27979;&#x8BD5; &amp&copy; &notit; &#150; & &#; 👍 &#x1F44D;";
+
+    #[test]
+    fn test_decoder() {
+        let mut out = String::new();
+        EntityDecoder::new(OneByte(INPUT.as_bytes()))
+            .read_to_string(&mut out)
+            .unwrap();
+        assert_eq!(out, decode(INPUT));
+
+        let mut out = String::new();
+        EntityDecoder::new(INPUT.as_bytes())
+            .read_to_string(&mut out)
+            .unwrap();
+        assert_eq!(out, decode(INPUT));
+    }
+
+    #[test]
+    fn test_decoder_bounded() {
+        // 很长的 `&aaaa…` 不会使缓冲区无限增长。
+        let input = format!("&{};", "a".repeat(1 << 16));
+        let mut decoder = EntityDecoder::new(OneByte(input.as_bytes()));
+        let mut out = Vec::new();
+        let mut buf = [0; 7];
+        loop {
+            let n = decoder.read(&mut buf).unwrap();
+            assert!(decoder.input.len() <= MAX_PENDING);
+            if n == 0 {
+                break;
+            }
+            out.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(out, input.as_bytes());
+
+        // 暂缓上限之内的字符引用仍可跨越块边界。
+        let input = "&CounterClockwiseContourIntegral; &#x00000000000000000000000006D4B;";
+        let mut out = String::new();
+        EntityDecoder::new(OneByte(input.as_bytes()))
+            .read_to_string(&mut out)
+            .unwrap();
+        assert_eq!(out, "\u{2233} 测");
+    }
+
+    #[test]
+    fn test_decoder_errors() {
+        let options = DecodeOptions::new().policy(ErrorPolicy::Strict);
+        let mut decoder = EntityDecoder::with_options(OneByte("测试 &#xD800;".as_bytes()), options);
+        let err = decoder.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let err = err.into_inner().unwrap().downcast::<DecodeError>().unwrap();
+        assert_eq!(err.offset, 7);
+        assert_eq!(err.kind, DecodeErrorKind::Surrogate);
+
+        let mut decoder = EntityDecoder::new(&b"ok \xff"[..]);
+        let err = decoder.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_encoder() {
+        let mut encoder = EntityEncoder::new(Vec::new());
+        for b in INPUT.bytes() {
+            encoder.write_all(&[b]).unwrap();
+        }
+        assert_eq!(encoder.finish().unwrap(), encode(INPUT).as_bytes());
+
+        let mut encoder = EntityEncoder::new(Vec::new());
+        encoder.write_all(&"测".as_bytes()[..2]).unwrap();
+        assert_eq!(encoder.finish().unwrap_err().kind(), ErrorKind::InvalidData);
+
+        let mut encoder = EntityEncoder::new(Vec::new());
+        assert!(encoder.write_all(b"ok \xff").is_err());
+    }
+}
@@ -0,0 +1,58 @@
+use core::error::Error;
+use core::fmt;
+
+/// Base32 解析失败的原因。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeErrorKind {
+    /// 不属于当前字母表的字符，或在不忽略空白时遇到的空白字符。
+    InvalidByte,
+    /// 有效字符的数量除以 8 余 1、3 或 6，无法构成完整的字节；开启校验时也表示缺少校验字符。
+    InvalidLength,
+    /// 填充 `=` 的数量或位置不正确，或不符合 [`Padding`](super::Padding) 的要求。
+    InvalidPadding,
+    /// 最后一个字符中未使用的低位不为零，如 `MZ======`。
+    TrailingBits,
+    /// Crockford 校验字符与内容不符。
+    CheckMismatch,
+    /// 解码结果不是有效的 UTF-8，见 [`Decoder::decode_str`](crate::Decoder::decode_str)。
+    InvalidUtf8,
+}
+
+impl fmt::Display for DecodeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DecodeErrorKind::InvalidByte => "不是 Base32 字母表中的字符",
+            DecodeErrorKind::InvalidLength => "有效字符的数量不正确",
+            DecodeErrorKind::InvalidPadding => "填充不正确",
+            DecodeErrorKind::TrailingBits => "末尾未使用的位不为零",
+            DecodeErrorKind::CheckMismatch => "校验字符不匹配",
+            DecodeErrorKind::InvalidUtf8 => "解码结果不是有效的 UTF-8",
+        })
+    }
+}
+
+/// 解码 Base32 时遇到的错误。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::base32::{decode, DecodeErrorKind};
+/// let err = decode("MZXW1===").unwrap_err();
+/// assert_eq!(err.offset, 4);
+/// assert_eq!(err.kind, DecodeErrorKind::InvalidByte);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeError {
+    /// 出错位置在输入中的字节偏移。
+    pub offset: usize,
+    /// 出错原因。
+    pub kind: DecodeErrorKind,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "第 {} 字节处的 Base32 无效：{}", self.offset, self.kind)
+    }
+}
+
+impl Error for DecodeError {}
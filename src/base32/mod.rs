@@ -0,0 +1,380 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+use core::str;
+
+mod error;
+mod options;
+
+pub use crate::base64::Padding;
+pub use error::{DecodeError, DecodeErrorKind};
+pub use options::{Alphabet, DecodeOptions, EncodeOptions};
+
+use options::{check_symbol, check_value, INVALID};
+
+/// 每次编码的输入字节数，是 5 的倍数，编码结果正好填满 [`encode_into`] 的缓冲区。
+const ENCODE_CHUNK: usize = 640;
+
+/// 编码的核心实现，将结果写入 `out`。
+fn encode_into<W: Write + ?Sized>(
+    bytes: &[u8],
+    options: &EncodeOptions,
+    out: &mut W,
+) -> fmt::Result {
+    let table = options.alphabet.encode_table();
+    let padding = options.padding && options.alphabet.pads();
+    let mut checksum = 0;
+    let mut buf = [0; ENCODE_CHUNK / 5 * 8];
+    for chunk in bytes.chunks(ENCODE_CHUNK) {
+        let mut n = 0;
+        for group in chunk.chunks(5) {
+            let bits = (0..5).fold(0u64, |bits, i| {
+                bits << 8 | u64::from(group.get(i).copied().unwrap_or_default())
+            });
+            // 1 到 5 个字节分别编码为 2、4、5、7、8 个字符。
+            let symbols = (group.len() * 8).div_ceil(5);
+            for k in 0..symbols {
+                let value = (bits >> (35 - 5 * k) & 0x1F) as usize;
+                checksum = (checksum * 32 + value as u32) % 37;
+                buf[n] = table[value];
+                n += 1;
+            }
+            if padding {
+                for _ in symbols..8 {
+                    buf[n] = b'=';
+                    n += 1;
+                }
+            }
+        }
+        // 编码结果都是 ASCII 字符。
+        out.write_str(str::from_utf8(&buf[..n]).unwrap_or_default())?;
+    }
+    if options.check && options.alphabet == Alphabet::Crockford {
+        out.write_char(char::from(check_symbol(checksum)))?;
+    }
+    Ok(())
+}
+
+/// 解码的核心实现，将结果追加到 `out`。
+fn decode_into(
+    input: &[u8],
+    options: &DecodeOptions,
+    out: &mut Vec<u8>,
+) -> Result<(), DecodeError> {
+    let error = |offset, kind| Err(DecodeError { offset, kind });
+    let crockford = options.alphabet == Alphabet::Crockford;
+    let skip = |b: u8| (options.whitespace && b.is_ascii_whitespace()) || (crockford && b == b'-');
+
+    // 开启校验时，最后一个有效字符是校验字符。
+    let (data, check) = match options.check && crockford {
+        true => match input.iter().rposition(|&b| !skip(b)) {
+            Some(i) => (&input[..i], Some((i, input[i]))),
+            None => return error(input.len(), DecodeErrorKind::InvalidLength),
+        },
+        false => (input, None),
+    };
+
+    let table = options.alphabet.decode_table();
+    let mut bits = 0u64;
+    let mut symbols = 0;
+    let mut last = 0;
+    let mut checksum = 0;
+    let mut padding: Option<(usize, usize)> = None;
+    for (i, &b) in data.iter().enumerate() {
+        if skip(b) {
+            continue;
+        }
+        if b == b'=' && !crockford {
+            let (start, count) = padding.unwrap_or((i, 0));
+            padding = Some((start, count + 1));
+            continue;
+        }
+        if padding.is_some() {
+            return error(i, DecodeErrorKind::InvalidPadding);
+        }
+        let value = table[usize::from(b)];
+        if value == INVALID {
+            return error(i, DecodeErrorKind::InvalidByte);
+        }
+        checksum = (checksum * 32 + u32::from(value)) % 37;
+        bits = bits << 5 | u64::from(value);
+        symbols += 1;
+        last = i;
+        if symbols == 8 {
+            out.extend_from_slice(&bits.to_be_bytes()[3..]);
+            bits = 0;
+            symbols = 0;
+        }
+    }
+
+    let expected = match symbols {
+        0 => 0,
+        2 | 4 | 5 | 7 => 8 - symbols,
+        _ => return error(last, DecodeErrorKind::InvalidLength),
+    };
+    let pads = padding.map_or(0, |(_, count)| count);
+    let valid = match options.padding {
+        _ if pads > expected => false,
+        Padding::Optional => pads == 0 || pads == expected,
+        Padding::Required => crockford || pads == expected,
+        Padding::Forbidden => pads == 0,
+    };
+    if !valid {
+        let offset = padding.map_or(data.len(), |(start, _)| start);
+        return error(offset, DecodeErrorKind::InvalidPadding);
+    }
+    if symbols > 0 {
+        // 剩余的字符解码为整数个字节，多出的低位必须为零。
+        let unused = symbols * 5 % 8;
+        if bits & ((1 << unused) - 1) != 0 {
+            return error(last, DecodeErrorKind::TrailingBits);
+        }
+        let bytes = (bits >> unused).to_be_bytes();
+        out.extend_from_slice(&bytes[8 - symbols * 5 / 8..]);
+    }
+
+    if let Some((i, b)) = check {
+        match check_value(b) {
+            None => return error(i, DecodeErrorKind::InvalidByte),
+            Some(value) if value != checksum => return error(i, DecodeErrorKind::CheckMismatch),
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// 将字节编码为标准的 Base32 文本（RFC 4648），末尾带填充。
+///
+/// # 说明
+///
+/// 输入可以是任意字节，也可以是字符串（按 UTF-8 字节编码）。使用 Base32hex、Crockford 字母表或去掉填充时，请使用 [`EncodeOptions`]。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::base32::encode;
+/// assert_eq!(encode("foobar"), "MZXW6YTBOI======");
+/// assert_eq!(encode(b"Hello!\xde\xad\xbe\xef"), "JBSWY3DPEHPK3PXP");
+/// ```
+pub fn encode(input: impl AsRef<[u8]>) -> String {
+    EncodeOptions::new().encode(input)
+}
+
+/// 将字节编码为标准的 Base32 文本并写入 `out`，不分配中间字符串。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::base32::encode_to;
+/// let mut uri = String::from("otpauth://totp/demo?secret=");
+/// encode_to(b"Hello!\xde\xad\xbe\xef", &mut uri).unwrap();
+/// assert_eq!(uri, "otpauth://totp/demo?secret=JBSWY3DPEHPK3PXP");
+/// ```
+pub fn encode_to<W: Write + ?Sized>(input: impl AsRef<[u8]>, out: &mut W) -> fmt::Result {
+    EncodeOptions::new().encode_to(input, out)
+}
+
+/// 将标准的 Base32 文本解码为字节。
+///
+/// # 说明
+///
+/// 大小写均可，输入中的 ASCII 空白字符会被忽略，末尾的填充可有可无，因此可以直接解码用户输入的 TOTP 密钥。遇到字母表之外的字符、长度或填充不正确时返回错误。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::base32::decode;
+/// assert_eq!(decode("MZXW6YTBOI======").unwrap(), b"foobar");
+/// assert_eq!(decode("jbsw y3dp ehpk 3pxp").unwrap(), b"Hello!\xde\xad\xbe\xef");
+/// assert!(decode("MZXW6Y").is_err());
+/// ```
+///
+/// # 注意事项
+///
+/// - 解码 Base32hex、Crockford 字母表或要求严格的格式时，请使用 [`DecodeOptions`]。
+pub fn decode(input: impl AsRef<[u8]>) -> Result<Vec<u8>, DecodeError> {
+    DecodeOptions::new().decode(input)
+}
+
+/// 将标准的 Base32 文本解码，并将结果追加到 `out`。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::base32::decode_to;
+/// let mut out = b"> ".to_vec();
+/// decode_to("NBUQ", &mut out).unwrap();
+/// assert_eq!(out, b"> hi");
+/// ```
+pub fn decode_to(input: impl AsRef<[u8]>, out: &mut Vec<u8>) -> Result<(), DecodeError> {
+    DecodeOptions::new().decode_to(input, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RFC 4648 第 10 节的测试向量：原文、Base32、Base32hex。
+    const VECTORS: [(&str, &str, &str); 7] = [
+        ("", "", ""),
+        ("f", "MY======", "CO======"),
+        ("fo", "MZXQ====", "CPNG===="),
+        ("foo", "MZXW6===", "CPNMU==="),
+        ("foob", "MZXW6YQ=", "CPNMUOG="),
+        ("fooba", "MZXW6YTB", "CPNMUOJ1"),
+        ("foobar", "MZXW6YTBOI======", "CPNMUOJ1E8======"),
+    ];
+
+    #[test]
+    fn test_rfc4648() {
+        let hex = EncodeOptions::new().alphabet(Alphabet::Hex);
+        let hex_decode = DecodeOptions::new().alphabet(Alphabet::Hex);
+        for (plain, standard, extended) in VECTORS {
+            assert_eq!(encode(plain), standard);
+            assert_eq!(decode(standard).unwrap(), plain.as_bytes());
+            assert_eq!(hex.encode(plain), extended);
+            assert_eq!(hex_decode.decode(extended).unwrap(), plain.as_bytes());
+
+            let unpadded = standard.trim_end_matches('=');
+            assert_eq!(EncodeOptions::new().padding(false).encode(plain), unpadded);
+            assert_eq!(decode(unpadded).unwrap(), plain.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_crockford() {
+        let encode = EncodeOptions::new().alphabet(Alphabet::Crockford);
+        let decode = DecodeOptions::new().alphabet(Alphabet::Crockford);
+        assert_eq!(encode.encode("f"), "CR");
+        assert_eq!(encode.encode("foobar"), "CSQPYRK1E8");
+        assert_eq!(encode.padding(true).encode("foobar"), "CSQPYRK1E8");
+        assert_eq!(decode.decode("CSQPYRK1E8").unwrap(), b"foobar");
+
+        // 大小写、容易混淆的字母和分隔符。
+        assert_eq!(decode.decode("csqp-yrk1-e8").unwrap(), b"foobar");
+        assert_eq!(decode.decode("CSQPYRKIE8").unwrap(), b"foobar");
+        assert_eq!(decode.decode("CSQPYRKlE8").unwrap(), b"foobar");
+        assert_eq!(decode.decode("0o").unwrap(), decode.decode("00").unwrap());
+        let err = decode.decode("CSQPYRKUE8").unwrap_err();
+        assert_eq!((err.offset, err.kind), (7, DecodeErrorKind::InvalidByte));
+        assert_eq!(
+            decode.decode("CR==").unwrap_err().kind,
+            DecodeErrorKind::InvalidByte
+        );
+        assert!(DecodeOptions::new().decode("csqp-yrk1-e8").is_err());
+    }
+
+    #[test]
+    fn test_crockford_check() {
+        let encode = EncodeOptions::new()
+            .alphabet(Alphabet::Crockford)
+            .check(true);
+        let decode = DecodeOptions::new()
+            .alphabet(Alphabet::Crockford)
+            .check(true);
+        let cases: [(&[u8], &str); 9] = [
+            (b"", "0"),
+            (b"f", "CR1"),
+            (b"foobar", "CSQPYRK1E8R"),
+            (
+                b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09",
+                "000G40R40M30E209Y",
+            ),
+            (b"\x08", "10*"),
+            (b"\x24", "4G~"),
+            (b"\x1b", "3C$"),
+            (b"\x12", "28="),
+            (b"\x09", "14U"),
+        ];
+        for (bytes, encoded) in cases {
+            assert_eq!(encode.encode(bytes), encoded);
+            assert_eq!(decode.decode(encoded).unwrap(), bytes);
+            assert_eq!(decode.decode(encoded.to_lowercase()).unwrap(), bytes);
+        }
+
+        assert_eq!(decode.decode("CSQP-YRK1-E8-R\n").unwrap(), b"foobar");
+        let err = decode.decode("CSQPYRK1E8S").unwrap_err();
+        assert_eq!((err.offset, err.kind), (10, DecodeErrorKind::CheckMismatch));
+        let err = decode.decode("CSQPYRK1E8!").unwrap_err();
+        assert_eq!((err.offset, err.kind), (10, DecodeErrorKind::InvalidByte));
+        let err = decode.decode(" - ").unwrap_err();
+        assert_eq!((err.offset, err.kind), (3, DecodeErrorKind::InvalidLength));
+        // 校验字符只能出现在末尾。
+        let err = decode.decode("1*01").unwrap_err();
+        assert_eq!((err.offset, err.kind), (1, DecodeErrorKind::InvalidByte));
+
+        // 标准字母表不使用校验字符。
+        let options = EncodeOptions::new().check(true);
+        assert_eq!(options.encode("f"), "MY======");
+    }
+
+    #[test]
+    fn test_decode_padding() {
+        let required = DecodeOptions::new().padding(Padding::Required);
+        assert_eq!(required.decode("MY======").unwrap(), b"f");
+        let err = required.decode("MY").unwrap_err();
+        assert_eq!((err.offset, err.kind), (2, DecodeErrorKind::InvalidPadding));
+
+        let forbidden = DecodeOptions::new().padding(Padding::Forbidden);
+        assert_eq!(forbidden.decode("MY").unwrap(), b"f");
+        let err = forbidden.decode("MY======").unwrap_err();
+        assert_eq!((err.offset, err.kind), (2, DecodeErrorKind::InvalidPadding));
+
+        for input in [
+            "MY=====",
+            "MY=======",
+            "MZXW6YQ==",
+            "MZXW6YTB=",
+            "=",
+            "MY======MY",
+        ] {
+            assert_eq!(
+                decode(input).unwrap_err().kind,
+                DecodeErrorKind::InvalidPadding,
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let cases = [
+            ("MZXW1===", 4, DecodeErrorKind::InvalidByte),
+            ("MZXW6YTB\tM", 9, DecodeErrorKind::InvalidLength),
+            ("MZX", 2, DecodeErrorKind::InvalidLength),
+            ("MZXW6Y", 5, DecodeErrorKind::InvalidLength),
+            ("MZ======", 1, DecodeErrorKind::TrailingBits),
+            ("MZXW7===", 4, DecodeErrorKind::TrailingBits),
+        ];
+        for (input, offset, kind) in cases {
+            let err = decode(input).unwrap_err();
+            assert_eq!(err.offset, offset, "{input}");
+            assert_eq!(err.kind, kind, "{input}");
+        }
+        let options = DecodeOptions::new().whitespace(false);
+        let err = options.decode("MZXW 6===").unwrap_err();
+        assert_eq!((err.offset, err.kind), (4, DecodeErrorKind::InvalidByte));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let bytes: Vec<u8> = (0..1500).map(|i| (i * 13 + i / 256) as u8).collect();
+        for len in [0, 1, 2, 3, 4, 5, 6, 639, 640, 641, 1500] {
+            let bytes = &bytes[..len];
+            for alphabet in [Alphabet::Standard, Alphabet::Hex, Alphabet::Crockford] {
+                for (padding, check) in [(false, false), (true, false), (true, true)] {
+                    let encoded = EncodeOptions::new()
+                        .alphabet(alphabet)
+                        .padding(padding)
+                        .check(check)
+                        .encode(bytes);
+                    let options = DecodeOptions::new()
+                        .alphabet(alphabet)
+                        .check(check && alphabet == Alphabet::Crockford);
+                    assert_eq!(options.decode(&encoded).unwrap(), bytes);
+                    assert_eq!(options.decode(encoded.to_lowercase()).unwrap(), bytes);
+                }
+            }
+        }
+    }
+}
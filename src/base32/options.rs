@@ -0,0 +1,278 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::Infallible;
+use core::fmt::{self, Write};
+
+use super::{decode_into, encode_into, DecodeError, DecodeErrorKind, Padding};
+use crate::codec::{decoded_str, Decoder, Encoder};
+
+/// 标准字母表，RFC 4648 第 6 节。
+const STANDARD: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// 扩展十六进制字母表，RFC 4648 第 7 节。
+const HEX: &[u8; 32] = b"0123456789ABCDEFGHIJKLMNOPQRSTUV";
+
+/// Crockford 字母表，去掉了容易混淆的 `I`、`L`、`O`、`U`。
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Crockford 校验字符中值为 32 到 36 的字符。
+const CHECK_SYMBOLS: &[u8; 5] = b"*~$=U";
+
+/// 解码表中表示无效字符的值。
+pub(crate) const INVALID: u8 = 0xFF;
+
+/// 由字母表生成解码表，大小写均可解码，`aliases` 中的字符按对应的值解码。
+const fn decode_table(alphabet: &[u8; 32], aliases: &[(u8, u8)]) -> [u8; 256] {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+    while i < 32 {
+        table[alphabet[i] as usize] = i as u8;
+        table[alphabet[i].to_ascii_lowercase() as usize] = i as u8;
+        i += 1;
+    }
+    let mut i = 0;
+    while i < aliases.len() {
+        let (alias, value) = aliases[i];
+        table[alias as usize] = value;
+        table[alias.to_ascii_lowercase() as usize] = value;
+        i += 1;
+    }
+    table
+}
+
+const STANDARD_DECODE: [u8; 256] = decode_table(STANDARD, &[]);
+const HEX_DECODE: [u8; 256] = decode_table(HEX, &[]);
+const CROCKFORD_DECODE: [u8; 256] = decode_table(CROCKFORD, &[(b'I', 1), (b'L', 1), (b'O', 0)]);
+
+/// Base32 使用的字母表。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Alphabet {
+    /// 标准字母表 `A-Z 2-7`，常见于 TOTP 密钥。
+    #[default]
+    Standard,
+    /// 扩展十六进制字母表 `0-9 A-V`，编码结果保持原数据的排序。
+    Hex,
+    /// Crockford 字母表，不使用填充；解码时忽略 `-`，并将 `I`、`L` 视为 `1`，`O` 视为 `0`，适合人工输入的编号。
+    Crockford,
+}
+
+impl Alphabet {
+    /// 编码表：5 位值到字符。
+    pub(crate) fn encode_table(self) -> &'static [u8; 32] {
+        match self {
+            Alphabet::Standard => STANDARD,
+            Alphabet::Hex => HEX,
+            Alphabet::Crockford => CROCKFORD,
+        }
+    }
+
+    /// 解码表：字符到 5 位值。
+    pub(crate) fn decode_table(self) -> &'static [u8; 256] {
+        match self {
+            Alphabet::Standard => &STANDARD_DECODE,
+            Alphabet::Hex => &HEX_DECODE,
+            Alphabet::Crockford => &CROCKFORD_DECODE,
+        }
+    }
+
+    /// 是否使用填充，Crockford 字母表不使用填充。
+    pub(crate) fn pads(self) -> bool {
+        self != Alphabet::Crockford
+    }
+}
+
+/// Crockford 校验字符：值为 0 到 31 时与数据字符相同，32 到 36 使用 `*~$=U`。
+pub(crate) fn check_symbol(value: u32) -> u8 {
+    match value as usize {
+        v @ 0..=31 => CROCKFORD[v],
+        v => CHECK_SYMBOLS[v - 32],
+    }
+}
+
+/// 解析 Crockford 校验字符，大小写均可。
+pub(crate) fn check_value(b: u8) -> Option<u32> {
+    match CROCKFORD_DECODE[usize::from(b)] {
+        INVALID => CHECK_SYMBOLS
+            .iter()
+            .position(|&c| c == b.to_ascii_uppercase())
+            .map(|i| i as u32 + 32),
+        v => Some(u32::from(v)),
+    }
+}
+
+/// Base32 的解码选项。
+///
+/// # 说明
+///
+/// 默认选项与 [`decode`](super::decode) 的行为一致：使用标准字母表，大小写均可，忽略空白字符，有没有填充都可以。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::base32::{Alphabet, DecodeOptions};
+/// let options = DecodeOptions::new().alphabet(Alphabet::Crockford).check(true);
+/// assert_eq!(options.decode("csqp-yrk1-e8r").unwrap(), b"foobar");
+/// assert_eq!(options.decode("CSQPYRKIE8R").unwrap(), b"foobar");
+/// assert!(options.decode("CSQPYRK1E8S").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeOptions {
+    pub(crate) alphabet: Alphabet,
+    pub(crate) padding: Padding,
+    pub(crate) whitespace: bool,
+    pub(crate) check: bool,
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        Self {
+            alphabet: Alphabet::Standard,
+            padding: Padding::Optional,
+            whitespace: true,
+            check: false,
+        }
+    }
+}
+
+impl DecodeOptions {
+    /// 创建默认的解码选项。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置字母表。
+    pub fn alphabet(mut self, alphabet: Alphabet) -> Self {
+        self.alphabet = alphabet;
+        self
+    }
+
+    /// 设置对末尾填充的要求，对 Crockford 字母表无效。
+    pub fn padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// 设置是否忽略输入中的 ASCII 空白字符，默认忽略；不忽略时空白字符视为无效字符。
+    pub fn whitespace(mut self, whitespace: bool) -> Self {
+        self.whitespace = whitespace;
+        self
+    }
+
+    /// 设置输入末尾是否带有 Crockford 校验字符，默认不带，只对 Crockford 字母表有效。
+    pub fn check(mut self, check: bool) -> Self {
+        self.check = check;
+        self
+    }
+
+    /// 按当前选项解码。
+    pub fn decode(&self, input: impl AsRef<[u8]>) -> Result<Vec<u8>, DecodeError> {
+        let input = input.as_ref();
+        let mut out = Vec::with_capacity(input.len() / 8 * 5 + 4);
+        decode_into(input, self, &mut out)?;
+        Ok(out)
+    }
+
+    /// 按当前选项解码，并将结果追加到 `out`；出错时 `out` 中可能已追加部分结果。
+    pub fn decode_to(&self, input: impl AsRef<[u8]>, out: &mut Vec<u8>) -> Result<(), DecodeError> {
+        decode_into(input.as_ref(), self, out)
+    }
+}
+
+/// Base32 的编码选项。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::base32::{Alphabet, EncodeOptions};
+/// assert_eq!(EncodeOptions::new().padding(false).encode("f"), "MY");
+/// assert_eq!(EncodeOptions::new().alphabet(Alphabet::Hex).encode("f"), "CO======");
+///
+/// let options = EncodeOptions::new().alphabet(Alphabet::Crockford).check(true);
+/// assert_eq!(options.encode("foobar"), "CSQPYRK1E8R");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncodeOptions {
+    pub(crate) alphabet: Alphabet,
+    pub(crate) padding: bool,
+    pub(crate) check: bool,
+}
+
+impl Default for EncodeOptions {
+    fn default() -> Self {
+        Self {
+            alphabet: Alphabet::Standard,
+            padding: true,
+            check: false,
+        }
+    }
+}
+
+impl EncodeOptions {
+    /// 创建默认的编码选项：标准字母表，带填充。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置字母表。
+    pub fn alphabet(mut self, alphabet: Alphabet) -> Self {
+        self.alphabet = alphabet;
+        self
+    }
+
+    /// 设置是否在末尾添加填充 `=`，默认添加；Crockford 字母表总是不添加。
+    pub fn padding(mut self, padding: bool) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// 设置是否在末尾添加 Crockford 校验字符，默认不添加，只对 Crockford 字母表有效。
+    pub fn check(mut self, check: bool) -> Self {
+        self.check = check;
+        self
+    }
+
+    /// 按当前选项编码。
+    pub fn encode(&self, input: impl AsRef<[u8]>) -> String {
+        let input = input.as_ref();
+        let mut out = String::with_capacity(input.len().div_ceil(5) * 8 + 1);
+        let _ = encode_into(input, self, &mut out);
+        out
+    }
+
+    /// 按当前选项编码并写入 `out`，不分配中间字符串。
+    pub fn encode_to<W: Write + ?Sized>(
+        &self,
+        input: impl AsRef<[u8]>,
+        out: &mut W,
+    ) -> fmt::Result {
+        encode_into(input.as_ref(), self, out)
+    }
+}
+
+impl Decoder for DecodeOptions {
+    type Error = DecodeError;
+
+    fn decode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, DecodeError> {
+        decoded_str(self.decode(input)?, |offset| DecodeError {
+            offset,
+            kind: DecodeErrorKind::InvalidUtf8,
+        })
+    }
+
+    fn decode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, DecodeError> {
+        self.decode(input).map(Cow::Owned)
+    }
+}
+
+impl Encoder for EncodeOptions {
+    type Error = Infallible;
+
+    fn encode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, Infallible> {
+        Ok(Cow::Owned(self.encode(input)))
+    }
+
+    fn encode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, Infallible> {
+        Ok(Cow::Owned(self.encode(input).into_bytes()))
+    }
+}
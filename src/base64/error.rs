@@ -0,0 +1,55 @@
+use core::error::Error;
+use core::fmt;
+
+/// Base64 解析失败的原因。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeErrorKind {
+    /// 不属于当前字母表的字符，或在不忽略空白时遇到的空白字符。
+    InvalidByte,
+    /// 有效字符的数量除以 4 余 1，无法构成完整的字节。
+    InvalidLength,
+    /// 填充 `=` 的数量或位置不正确，或不符合 [`Padding`](super::Padding) 的要求。
+    InvalidPadding,
+    /// 最后一个字符中未使用的低位不为零，如 `Zh==`。
+    TrailingBits,
+    /// 解码结果不是有效的 UTF-8，见 [`Decoder::decode_str`](crate::Decoder::decode_str)。
+    InvalidUtf8,
+}
+
+impl fmt::Display for DecodeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DecodeErrorKind::InvalidByte => "不是 Base64 字母表中的字符",
+            DecodeErrorKind::InvalidLength => "有效字符的数量不正确",
+            DecodeErrorKind::InvalidPadding => "填充不正确",
+            DecodeErrorKind::TrailingBits => "末尾未使用的位不为零",
+            DecodeErrorKind::InvalidUtf8 => "解码结果不是有效的 UTF-8",
+        })
+    }
+}
+
+/// 解码 Base64 时遇到的错误。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::base64::{decode, DecodeErrorKind};
+/// let err = decode("Zm9v!mFy").unwrap_err();
+/// assert_eq!(err.offset, 4);
+/// assert_eq!(err.kind, DecodeErrorKind::InvalidByte);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeError {
+    /// 出错位置在输入中的字节偏移。
+    pub offset: usize,
+    /// 出错原因。
+    pub kind: DecodeErrorKind,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "第 {} 字节处的 Base64 无效：{}", self.offset, self.kind)
+    }
+}
+
+impl Error for DecodeError {}
@@ -0,0 +1,369 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+use core::str;
+
+mod error;
+mod options;
+#[cfg(feature = "std")]
+mod stream;
+
+pub use error::{DecodeError, DecodeErrorKind};
+pub use options::{Alphabet, DecodeOptions, EncodeOptions, Padding};
+#[cfg(feature = "std")]
+pub use stream::{Base64Decoder, Base64Encoder};
+
+use options::INVALID;
+
+/// 每次编码的输入字节数，是 3 的倍数，编码结果正好填满 [`encode_into`] 的缓冲区。
+const ENCODE_CHUNK: usize = 768;
+
+/// 按固定列宽换行写入编码结果，记录当前行已写入的字符数，以便分多次写入。
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Lines {
+    width: usize,
+    col: usize,
+}
+
+impl Lines {
+    /// 创建换行状态，`width` 为 0 时不换行。
+    pub(crate) fn new(width: usize) -> Self {
+        Self { width, col: 0 }
+    }
+
+    /// 写入 `s`，行满且还有后续内容时才写入 `\r\n`，因此结果末尾不会有多余的换行。
+    fn write<W: Write + ?Sized>(&mut self, mut s: &str, out: &mut W) -> fmt::Result {
+        if self.width == 0 {
+            return out.write_str(s);
+        }
+        while !s.is_empty() {
+            if self.col == self.width {
+                out.write_str("\r\n")?;
+                self.col = 0;
+            }
+            let n = s.len().min(self.width - self.col);
+            out.write_str(&s[..n])?;
+            self.col += n;
+            s = &s[n..];
+        }
+        Ok(())
+    }
+}
+
+/// 编码的核心实现，将结果写入 `out`；只有最后一组可以不足 3 个字节，分多次编码时前面各次的长度须为 3 的倍数。
+pub(crate) fn encode_into<W: Write + ?Sized>(
+    bytes: &[u8],
+    options: &EncodeOptions,
+    lines: &mut Lines,
+    out: &mut W,
+) -> fmt::Result {
+    let table = options.alphabet.encode_table();
+    let mut buf = [0; ENCODE_CHUNK / 3 * 4];
+    for chunk in bytes.chunks(ENCODE_CHUNK) {
+        let mut n = 0;
+        for group in chunk.chunks(3) {
+            let byte = |i: usize| u32::from(group.get(i).copied().unwrap_or_default());
+            let bits = byte(0) << 16 | byte(1) << 8 | byte(2);
+            for k in 0..=group.len() {
+                buf[n] = table[(bits >> (18 - 6 * k) & 0x3F) as usize];
+                n += 1;
+            }
+            if options.padding {
+                for _ in group.len()..3 {
+                    buf[n] = b'=';
+                    n += 1;
+                }
+            }
+        }
+        // 编码结果都是 ASCII 字符。
+        lines.write(str::from_utf8(&buf[..n]).unwrap_or_default(), out)?;
+    }
+    Ok(())
+}
+
+/// 解码的核心实现，将结果追加到 `out`。
+pub(crate) fn decode_into(
+    input: &[u8],
+    options: &DecodeOptions,
+    out: &mut Vec<u8>,
+) -> Result<(), DecodeError> {
+    let error = |offset, kind| Err(DecodeError { offset, kind });
+    let table = options.alphabet.decode_table();
+    let mut bits = 0u32;
+    let mut symbols = 0;
+    let mut last = 0;
+    let mut padding: Option<(usize, usize)> = None;
+    for (i, &b) in input.iter().enumerate() {
+        if options.whitespace && b.is_ascii_whitespace() {
+            continue;
+        }
+        if b == b'=' {
+            let (start, count) = padding.unwrap_or((i, 0));
+            padding = Some((start, count + 1));
+            continue;
+        }
+        if padding.is_some() {
+            return error(i, DecodeErrorKind::InvalidPadding);
+        }
+        let value = table[usize::from(b)];
+        if value == INVALID {
+            return error(i, DecodeErrorKind::InvalidByte);
+        }
+        bits = bits << 6 | u32::from(value);
+        symbols += 1;
+        last = i;
+        if symbols == 4 {
+            out.extend_from_slice(&bits.to_be_bytes()[1..]);
+            bits = 0;
+            symbols = 0;
+        }
+    }
+
+    let expected = match symbols {
+        0 => 0,
+        1 => return error(last, DecodeErrorKind::InvalidLength),
+        n => 4 - n,
+    };
+    let pads = padding.map_or(0, |(_, count)| count);
+    let valid = match options.padding {
+        _ if pads > expected => false,
+        Padding::Optional => pads == 0 || pads == expected,
+        Padding::Required => pads == expected,
+        Padding::Forbidden => pads == 0,
+    };
+    if !valid {
+        let offset = padding.map_or(input.len(), |(start, _)| start);
+        return error(offset, DecodeErrorKind::InvalidPadding);
+    }
+    if symbols > 0 {
+        // 剩余 2 个字符解码为 1 个字节，3 个字符解码为 2 个字节，多出的低位必须为零。
+        let unused = symbols * 6 % 8;
+        if bits & ((1 << unused) - 1) != 0 {
+            return error(last, DecodeErrorKind::TrailingBits);
+        }
+        let bytes = (bits >> unused).to_be_bytes();
+        out.extend_from_slice(&bytes[4 - (symbols - 1)..]);
+    }
+    Ok(())
+}
+
+/// 将字节编码为标准的 Base64 文本（RFC 4648），末尾带填充。
+///
+/// # 说明
+///
+/// 输入可以是任意字节，也可以是字符串（按 UTF-8 字节编码）。使用 URL 安全的字母表、去掉填充或按 MIME 格式换行时，请使用 [`EncodeOptions`]。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::base64::encode;
+/// assert_eq!(encode("foobar"), "Zm9vYmFy");
+/// assert_eq!(encode("测试"), "5rWL6K+V");
+/// assert_eq!(encode([0xfb, 0xff]), "+/8=");
+/// ```
+pub fn encode(input: impl AsRef<[u8]>) -> String {
+    EncodeOptions::new().encode(input)
+}
+
+/// 将字节编码为标准的 Base64 文本并写入 `out`，不分配中间字符串。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::base64::encode_to;
+/// let mut url = String::from("data:text/plain;base64,");
+/// encode_to("hi", &mut url).unwrap();
+/// assert_eq!(url, "data:text/plain;base64,aGk=");
+/// ```
+pub fn encode_to<W: Write + ?Sized>(input: impl AsRef<[u8]>, out: &mut W) -> fmt::Result {
+    EncodeOptions::new().encode_to(input, out)
+}
+
+/// 将标准的 Base64 文本解码为字节。
+///
+/// # 说明
+///
+/// 输入中的 ASCII 空白字符（如 MIME 格式的换行）会被忽略，末尾的填充可有可无。遇到字母表之外的字符、长度或填充不正确时返回错误。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::base64::decode;
+/// assert_eq!(decode("Zm9vYmFy").unwrap(), b"foobar");
+/// assert_eq!(decode("Zm9v\r\nYg==").unwrap(), b"foob");
+/// assert_eq!(decode("Zm9vYg").unwrap(), b"foob");
+/// assert!(decode("Zm9vY").is_err());
+/// ```
+///
+/// # 注意事项
+///
+/// - 解码 URL 安全的字母表或要求严格的格式时，请使用 [`DecodeOptions`]。
+/// - 需要文本时，请用 [`String::from_utf8`] 转换结果。
+pub fn decode(input: impl AsRef<[u8]>) -> Result<Vec<u8>, DecodeError> {
+    DecodeOptions::new().decode(input)
+}
+
+/// 将标准的 Base64 文本解码，并将结果追加到 `out`。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::base64::decode_to;
+/// let mut out = b"> ".to_vec();
+/// decode_to("aGk=", &mut out).unwrap();
+/// assert_eq!(out, b"> hi");
+/// ```
+pub fn decode_to(input: impl AsRef<[u8]>, out: &mut Vec<u8>) -> Result<(), DecodeError> {
+    DecodeOptions::new().decode_to(input, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RFC 4648 第 10 节的测试向量。
+    const VECTORS: [(&str, &str); 7] = [
+        ("", ""),
+        ("f", "Zg=="),
+        ("fo", "Zm8="),
+        ("foo", "Zm9v"),
+        ("foob", "Zm9vYg=="),
+        ("fooba", "Zm9vYmE="),
+        ("foobar", "Zm9vYmFy"),
+    ];
+
+    #[test]
+    fn test_rfc4648() {
+        for (plain, encoded) in VECTORS {
+            assert_eq!(encode(plain), encoded);
+            assert_eq!(decode(encoded).unwrap(), plain.as_bytes());
+            let unpadded = encoded.trim_end_matches('=');
+            let options = EncodeOptions::new().padding(false);
+            assert_eq!(options.encode(plain), unpadded);
+            assert_eq!(decode(unpadded).unwrap(), plain.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_alphabet() {
+        let bytes = [0xfb, 0xef, 0xbe, 0xff];
+        assert_eq!(encode(bytes), "++++/w==");
+        let url_safe = EncodeOptions::new().alphabet(Alphabet::UrlSafe);
+        assert_eq!(url_safe.encode(bytes), "----_w==");
+
+        let options = DecodeOptions::new().alphabet(Alphabet::UrlSafe);
+        assert_eq!(options.decode("----_w").unwrap(), bytes);
+        assert_eq!(
+            options.decode("++++/w==").unwrap_err().kind,
+            DecodeErrorKind::InvalidByte
+        );
+        assert_eq!(
+            decode("----_w==").unwrap_err().kind,
+            DecodeErrorKind::InvalidByte
+        );
+    }
+
+    #[test]
+    fn test_mime() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let encoded = EncodeOptions::mime().encode(&bytes);
+        let lines: Vec<&str> = encoded.split("\r\n").collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[..4].iter().all(|line| line.len() == 76));
+        assert_eq!(lines[4].len(), 40);
+        assert!(lines[4].ends_with("+fr7/P3+/w=="));
+        assert_eq!(lines.concat(), encode(&bytes));
+        assert_eq!(decode(&encoded).unwrap(), bytes);
+
+        // 长度正好是整行时末尾不换行。
+        let encoded = EncodeOptions::new().wrap(4).encode("foobar");
+        assert_eq!(encoded, "Zm9v\r\nYmFy");
+        let mut out = String::new();
+        EncodeOptions::new()
+            .wrap(3)
+            .encode_to("foob", &mut out)
+            .unwrap();
+        assert_eq!(out, "Zm9\r\nvYg\r\n==");
+    }
+
+    #[test]
+    fn test_decode_whitespace() {
+        assert_eq!(decode(" Zm9v\tYm\nFy \r\n").unwrap(), b"foobar");
+        assert_eq!(decode("Zg =\n=").unwrap(), b"f");
+        let options = DecodeOptions::new().whitespace(false);
+        let err = options.decode("Zm9v\nYmFy").unwrap_err();
+        assert_eq!((err.offset, err.kind), (4, DecodeErrorKind::InvalidByte));
+    }
+
+    #[test]
+    fn test_decode_padding() {
+        let required = DecodeOptions::new().padding(Padding::Required);
+        assert_eq!(required.decode("Zg==").unwrap(), b"f");
+        assert_eq!(required.decode("Zm9v").unwrap(), b"foo");
+        let err = required.decode("Zg").unwrap_err();
+        assert_eq!((err.offset, err.kind), (2, DecodeErrorKind::InvalidPadding));
+        assert!(required.decode("Zg=").is_err());
+
+        let forbidden = DecodeOptions::new().padding(Padding::Forbidden);
+        assert_eq!(forbidden.decode("Zg").unwrap(), b"f");
+        let err = forbidden.decode("Zg==").unwrap_err();
+        assert_eq!((err.offset, err.kind), (2, DecodeErrorKind::InvalidPadding));
+
+        for input in ["Zg=", "Zg===", "Zm8==", "Zm9v=", "=", "Zg==Zg==", "Zg=a"] {
+            assert_eq!(
+                decode(input).unwrap_err().kind,
+                DecodeErrorKind::InvalidPadding,
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let cases = [
+            ("Zm9v!mFy", 4, DecodeErrorKind::InvalidByte),
+            ("Zm9v测", 4, DecodeErrorKind::InvalidByte),
+            ("Zm9vY", 4, DecodeErrorKind::InvalidLength),
+            ("Zm9vY\n", 4, DecodeErrorKind::InvalidLength),
+            ("Zh==", 1, DecodeErrorKind::TrailingBits),
+            ("Zm9=", 2, DecodeErrorKind::TrailingBits),
+            ("Zg=a", 3, DecodeErrorKind::InvalidPadding),
+        ];
+        for (input, offset, kind) in cases {
+            let err = decode(input).unwrap_err();
+            assert_eq!(err.offset, offset, "{input}");
+            assert_eq!(err.kind, kind, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let bytes: Vec<u8> = (0..2000).map(|i| (i * 7 + i / 256) as u8).collect();
+        for len in [0, 1, 2, 3, 767, 768, 769, 2000] {
+            let bytes = &bytes[..len];
+            for alphabet in [Alphabet::Standard, Alphabet::UrlSafe] {
+                for padding in [false, true] {
+                    for wrap in [0, 1, 76] {
+                        let encoded = EncodeOptions::new()
+                            .alphabet(alphabet)
+                            .padding(padding)
+                            .wrap(wrap)
+                            .encode(bytes);
+                        let decoded = DecodeOptions::new().alphabet(alphabet).decode(&encoded);
+                        assert_eq!(decoded.unwrap(), bytes);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_to() {
+        let mut out = b"foo".to_vec();
+        decode_to("YmFy", &mut out).unwrap();
+        assert_eq!(out, b"foobar");
+        let mut out = String::from("> ");
+        encode_to("foo", &mut out).unwrap();
+        assert_eq!(out, "> Zm9v");
+    }
+}
@@ -0,0 +1,244 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::Infallible;
+use core::fmt::{self, Write};
+
+use super::{decode_into, encode_into, DecodeError, DecodeErrorKind, Lines};
+use crate::codec::{decoded_str, Decoder, Encoder};
+
+/// 标准字母表，RFC 4648 第 4 节。
+const STANDARD: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// URL 和文件名安全的字母表，RFC 4648 第 5 节。
+const URL_SAFE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// 解码表中表示无效字符的值。
+pub(crate) const INVALID: u8 = 0xFF;
+
+/// 由字母表生成解码表，不属于字母表的字节对应 [`INVALID`]。
+const fn decode_table(alphabet: &[u8; 64]) -> [u8; 256] {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+    while i < 64 {
+        table[alphabet[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+}
+
+const STANDARD_DECODE: [u8; 256] = decode_table(STANDARD);
+const URL_SAFE_DECODE: [u8; 256] = decode_table(URL_SAFE);
+
+/// Base64 使用的字母表。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Alphabet {
+    /// 标准字母表，使用 `+` 和 `/`。
+    #[default]
+    Standard,
+    /// URL 和文件名安全的字母表，使用 `-` 和 `_`，常见于 JWT 和 URL 参数。
+    UrlSafe,
+}
+
+impl Alphabet {
+    /// 编码表：6 位值到字符。
+    pub(crate) fn encode_table(self) -> &'static [u8; 64] {
+        match self {
+            Alphabet::Standard => STANDARD,
+            Alphabet::UrlSafe => URL_SAFE,
+        }
+    }
+
+    /// 解码表：字符到 6 位值。
+    pub(crate) fn decode_table(self) -> &'static [u8; 256] {
+        match self {
+            Alphabet::Standard => &STANDARD_DECODE,
+            Alphabet::UrlSafe => &URL_SAFE_DECODE,
+        }
+    }
+}
+
+/// 解码时对末尾填充 `=` 的要求。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Padding {
+    /// 有没有填充都可以，但有填充时数量必须正确。
+    #[default]
+    Optional,
+    /// 必须带有正确的填充。
+    Required,
+    /// 不能带有填充。
+    Forbidden,
+}
+
+/// Base64 的解码选项。
+///
+/// # 说明
+///
+/// 默认选项与 [`decode`](super::decode) 的行为一致：使用标准字母表，忽略空白字符，有没有填充都可以。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::base64::{Alphabet, DecodeOptions, Padding};
+/// let options = DecodeOptions::new().alphabet(Alphabet::UrlSafe);
+/// assert_eq!(options.decode("-_8").unwrap(), b"\xfb\xff");
+/// assert!(options.padding(Padding::Required).decode("-_8").is_err());
+/// assert!(options.whitespace(false).decode("-_8=\n").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeOptions {
+    pub(crate) alphabet: Alphabet,
+    pub(crate) padding: Padding,
+    pub(crate) whitespace: bool,
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        Self {
+            alphabet: Alphabet::Standard,
+            padding: Padding::Optional,
+            whitespace: true,
+        }
+    }
+}
+
+impl DecodeOptions {
+    /// 创建默认的解码选项。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置字母表。
+    pub fn alphabet(mut self, alphabet: Alphabet) -> Self {
+        self.alphabet = alphabet;
+        self
+    }
+
+    /// 设置对末尾填充的要求。
+    pub fn padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// 设置是否忽略输入中的 ASCII 空白字符（如 MIME 的换行），默认忽略；不忽略时空白字符视为无效字符。
+    pub fn whitespace(mut self, whitespace: bool) -> Self {
+        self.whitespace = whitespace;
+        self
+    }
+
+    /// 按当前选项解码。
+    pub fn decode(&self, input: impl AsRef<[u8]>) -> Result<Vec<u8>, DecodeError> {
+        let input = input.as_ref();
+        let mut out = Vec::with_capacity(input.len() / 4 * 3 + 2);
+        decode_into(input, self, &mut out)?;
+        Ok(out)
+    }
+
+    /// 按当前选项解码，并将结果追加到 `out`；出错时 `out` 中可能已追加部分结果。
+    pub fn decode_to(&self, input: impl AsRef<[u8]>, out: &mut Vec<u8>) -> Result<(), DecodeError> {
+        decode_into(input.as_ref(), self, out)
+    }
+}
+
+/// Base64 的编码选项。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::base64::{Alphabet, EncodeOptions};
+/// let options = EncodeOptions::new().alphabet(Alphabet::UrlSafe).padding(false);
+/// assert_eq!(options.encode(b"\xfb\xff"), "-_8");
+///
+/// let mime = EncodeOptions::mime().encode([0; 60]);
+/// assert_eq!(mime.lines().map(str::len).collect::<Vec<_>>(), [76, 4]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncodeOptions {
+    pub(crate) alphabet: Alphabet,
+    pub(crate) padding: bool,
+    pub(crate) wrap: usize,
+}
+
+impl Default for EncodeOptions {
+    fn default() -> Self {
+        Self {
+            alphabet: Alphabet::Standard,
+            padding: true,
+            wrap: 0,
+        }
+    }
+}
+
+impl EncodeOptions {
+    /// 创建默认的编码选项：标准字母表，带填充，不换行。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建 MIME（RFC 2045）使用的编码选项：标准字母表，带填充，每 76 个字符以 `\r\n` 换行。
+    pub fn mime() -> Self {
+        Self::new().wrap(76)
+    }
+
+    /// 设置字母表。
+    pub fn alphabet(mut self, alphabet: Alphabet) -> Self {
+        self.alphabet = alphabet;
+        self
+    }
+
+    /// 设置是否在末尾添加填充 `=`，默认添加。
+    pub fn padding(mut self, padding: bool) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// 设置每行的字符数，行之间以 `\r\n` 分隔，最后一行之后不换行；为 0 时不换行。
+    pub fn wrap(mut self, width: usize) -> Self {
+        self.wrap = width;
+        self
+    }
+
+    /// 按当前选项编码。
+    pub fn encode(&self, input: impl AsRef<[u8]>) -> String {
+        let input = input.as_ref();
+        let mut out = String::with_capacity(input.len().div_ceil(3) * 4);
+        let _ = encode_into(input, self, &mut Lines::new(self.wrap), &mut out);
+        out
+    }
+
+    /// 按当前选项编码并写入 `out`，不分配中间字符串。
+    pub fn encode_to<W: Write + ?Sized>(
+        &self,
+        input: impl AsRef<[u8]>,
+        out: &mut W,
+    ) -> fmt::Result {
+        encode_into(input.as_ref(), self, &mut Lines::new(self.wrap), out)
+    }
+}
+
+impl Decoder for DecodeOptions {
+    type Error = DecodeError;
+
+    fn decode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, DecodeError> {
+        decoded_str(self.decode(input)?, |offset| DecodeError {
+            offset,
+            kind: DecodeErrorKind::InvalidUtf8,
+        })
+    }
+
+    fn decode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, DecodeError> {
+        self.decode(input).map(Cow::Owned)
+    }
+}
+
+impl Encoder for EncodeOptions {
+    type Error = Infallible;
+
+    fn encode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, Infallible> {
+        Ok(Cow::Owned(self.encode(input)))
+    }
+
+    fn encode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, Infallible> {
+        Ok(Cow::Owned(self.encode(input).into_bytes()))
+    }
+}
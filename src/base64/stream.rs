@@ -0,0 +1,331 @@
+use std::io::{self, ErrorKind, Read, Write};
+
+use super::{
+    decode_into, encode_into, DecodeError, DecodeErrorKind, DecodeOptions, EncodeOptions, Lines,
+};
+
+/// 每次从底层读取器读取的字节数。
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// 判断字节是否参与解码，忽略空白字符时空白字符不计入。
+fn significant(b: u8, options: &DecodeOptions) -> bool {
+    !(options.whitespace && b.is_ascii_whitespace())
+}
+
+/// 返回 `input` 中由完整的 4 字符组构成的前缀长度，末尾不完整的组留待读取更多输入后再解码。
+fn complete_len(input: &[u8], options: &DecodeOptions) -> usize {
+    let mut count = 0;
+    let mut end = 0;
+    for (i, &b) in input.iter().enumerate() {
+        if significant(b, options) {
+            count += 1;
+            if count % 4 == 0 {
+                end = i + 1;
+            }
+        }
+    }
+    end
+}
+
+/// 将写入的字节编码为 Base64，并写入底层的 [`Write`]。
+///
+/// # 说明
+///
+/// 写入的字节按 3 个一组编码，不足一组的字节暂存到下一次写入，按 MIME 格式换行时行的位置在多次写入之间保持连续，因此结果与对完整输入调用 [`EncodeOptions::encode`] 相同。写入完毕后必须调用 [`Base64Encoder::finish`]，写入最后一组和填充并取回底层的写入器。
+///
+/// # 示例
+///
+/// ```rust
+/// # use std::io::Write;
+/// # use utils_rust::base64::Base64Encoder;
+/// let mut encoder = Base64Encoder::new(Vec::new());
+/// encoder.write_all(b"foo").unwrap();
+/// encoder.write_all(b"ba").unwrap();
+/// assert_eq!(encoder.finish().unwrap(), b"Zm9vYmE=");
+/// ```
+#[derive(Debug)]
+pub struct Base64Encoder<W: Write> {
+    inner: W,
+    options: EncodeOptions,
+    lines: Lines,
+    /// 尚未凑满一组的输入字节。
+    pending: Vec<u8>,
+    /// 复用的编码输出缓冲区。
+    buf: String,
+}
+
+impl<W: Write> Base64Encoder<W> {
+    /// 使用默认编码选项创建编码器。
+    pub fn new(inner: W) -> Self {
+        Self::with_options(inner, EncodeOptions::new())
+    }
+
+    /// 使用指定的编码选项创建编码器。
+    pub fn with_options(inner: W, options: EncodeOptions) -> Self {
+        Self {
+            inner,
+            options,
+            lines: Lines::new(options.wrap),
+            pending: Vec::with_capacity(3),
+            buf: String::new(),
+        }
+    }
+
+    /// 获取底层写入器的引用。
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// 编码暂存的最后一组字节并返回底层写入器。
+    pub fn finish(mut self) -> io::Result<W> {
+        let pending = std::mem::take(&mut self.pending);
+        self.encode(&pending)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    fn encode(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.buf.clear();
+        let _ = encode_into(bytes, &self.options, &mut self.lines, &mut self.buf);
+        self.inner.write_all(self.buf.as_bytes())
+    }
+}
+
+impl<W: Write> Write for Base64Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut input = buf;
+        if !self.pending.is_empty() {
+            // 先凑满上一次暂存的一组。
+            let take = input.len().min(3 - self.pending.len());
+            self.pending.extend_from_slice(&input[..take]);
+            input = &input[take..];
+            if self.pending.len() < 3 {
+                return Ok(buf.len());
+            }
+            let pending = std::mem::take(&mut self.pending);
+            self.encode(&pending)?;
+            self.pending = pending;
+            self.pending.clear();
+        }
+        let whole = input.len() / 3 * 3;
+        self.encode(&input[..whole])?;
+        self.pending.extend_from_slice(&input[whole..]);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// 从底层的 [`Read`] 中读取 Base64 文本，并以解码后的字节提供读取。
+///
+/// # 说明
+///
+/// 输入按块读取，每次只解码由完整的 4 字符组构成的部分，被块边界截断的组会保留到读取更多输入后再解码，因此解码结果与对完整输入调用 [`DecodeOptions::decode`] 相同。
+///
+/// 输入不是有效的 Base64 时，读取返回 [`ErrorKind::InvalidData`] 错误，其中包装了 [`DecodeError`]，偏移相对于整个输入。
+///
+/// # 示例
+///
+/// ```rust
+/// # use std::io::Read;
+/// # use utils_rust::base64::Base64Decoder;
+/// let mut decoder = Base64Decoder::new("Zm9v\r\nYmFy".as_bytes());
+/// let mut out = Vec::new();
+/// decoder.read_to_end(&mut out).unwrap();
+/// assert_eq!(out, b"foobar");
+/// ```
+#[derive(Debug)]
+pub struct Base64Decoder<R: Read> {
+    inner: R,
+    options: DecodeOptions,
+    /// 已读取但尚未解码的字节。
+    input: Vec<u8>,
+    /// 已解码但尚未被读取的字节。
+    output: Vec<u8>,
+    /// `output` 中已被读取的字节数。
+    pos: usize,
+    /// 已解码的输入字节数，用于修正错误偏移。
+    consumed: usize,
+    /// 已解码的部分是否以填充结尾，之后不能再出现有效字符。
+    padded: bool,
+    eof: bool,
+}
+
+impl<R: Read> Base64Decoder<R> {
+    /// 使用默认解码选项创建解码器。
+    pub fn new(inner: R) -> Self {
+        Self::with_options(inner, DecodeOptions::new())
+    }
+
+    /// 使用指定的解码选项创建解码器。
+    pub fn with_options(inner: R, options: DecodeOptions) -> Self {
+        Self {
+            inner,
+            options,
+            input: Vec::new(),
+            output: Vec::new(),
+            pos: 0,
+            consumed: 0,
+            padded: false,
+            eof: false,
+        }
+    }
+
+    /// 获取底层读取器的引用。
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// 返回底层读取器，已读取但尚未解码的数据将被丢弃。
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// 读取下一块输入，并将其中由完整的组构成的部分解码到 `output`。
+    fn fill(&mut self) -> io::Result<()> {
+        if !self.eof {
+            let start = self.input.len();
+            self.input.resize(start + CHUNK_SIZE, 0);
+            let read = loop {
+                match self.inner.read(&mut self.input[start..]) {
+                    Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                    read => break read,
+                }
+            };
+            let n = read.inspect_err(|_| self.input.truncate(start))?;
+            self.input.truncate(start + n);
+            self.eof = n == 0;
+        }
+
+        let end = if self.eof {
+            self.input.len()
+        } else {
+            complete_len(&self.input, &self.options)
+        };
+        let segment = &self.input[..end];
+        let options = self.options;
+        let mut symbols = segment
+            .iter()
+            .enumerate()
+            .filter(|&(_, &b)| significant(b, &options));
+        let error = |offset, kind| {
+            let e = DecodeError { offset, kind };
+            io::Error::new(ErrorKind::InvalidData, e)
+        };
+        if self.padded {
+            if let Some((i, _)) = symbols.next() {
+                return Err(error(self.consumed + i, DecodeErrorKind::InvalidPadding));
+            }
+        }
+        self.output.clear();
+        self.pos = 0;
+        decode_into(segment, &options, &mut self.output)
+            .map_err(|e| error(self.consumed + e.offset, e.kind))?;
+        self.padded |= symbols.next_back().is_some_and(|(_, &b)| b == b'=');
+        self.consumed += end;
+        self.input.drain(..end);
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for Base64Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.output.len() {
+            if self.eof && self.input.is_empty() {
+                return Ok(0);
+            }
+            self.fill()?;
+        }
+        let n = (&self.output[self.pos..]).read(buf)?;
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base64::{decode, encode, Padding};
+
+    /// 每次只返回一个字节的读取器，用于覆盖所有可能的块边界。
+    struct OneByte<'a>(&'a [u8]);
+
+    impl Read for OneByte<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.0.len().min(buf.len()).min(1);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    fn bytes(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 31 + i / 7) as u8).collect()
+    }
+
+    fn read_all(mut reader: impl Read) -> io::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).map(|_| out)
+    }
+
+    #[test]
+    fn test_encoder() {
+        for len in [0, 1, 2, 3, 4, 100, 20000] {
+            let input = bytes(len);
+            for options in [EncodeOptions::new(), EncodeOptions::mime().padding(false)] {
+                let mut encoder = Base64Encoder::with_options(Vec::new(), options);
+                for b in &input {
+                    encoder.write_all(&[*b]).unwrap();
+                }
+                assert_eq!(encoder.finish().unwrap(), options.encode(&input).as_bytes());
+
+                let mut encoder = Base64Encoder::with_options(Vec::new(), options);
+                for chunk in input.chunks(1000) {
+                    encoder.write_all(chunk).unwrap();
+                }
+                assert_eq!(encoder.finish().unwrap(), options.encode(&input).as_bytes());
+            }
+        }
+    }
+
+    #[test]
+    fn test_decoder() {
+        for len in [0, 1, 2, 3, 100, 20000] {
+            let input = bytes(len);
+            let encoded = EncodeOptions::mime().encode(&input);
+            let decoded = read_all(Base64Decoder::new(OneByte(encoded.as_bytes())));
+            assert_eq!(decoded.unwrap(), input);
+            let decoded = read_all(Base64Decoder::new(encoded.as_bytes()));
+            assert_eq!(decoded.unwrap(), input);
+        }
+
+        let unpadded = encode(bytes(100)).trim_end_matches('=').to_string();
+        let decoded = read_all(Base64Decoder::new(OneByte(unpadded.as_bytes())));
+        assert_eq!(decoded.unwrap(), decode(&unpadded).unwrap());
+    }
+
+    #[test]
+    fn test_decoder_errors() {
+        let error = |input: &str, options| {
+            let reader = Base64Decoder::with_options(OneByte(input.as_bytes()), options);
+            let err = read_all(reader).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidData);
+            *err.into_inner().unwrap().downcast::<DecodeError>().unwrap()
+        };
+        let options = DecodeOptions::new();
+        let err = error("Zm9v\r\nYm!y", options);
+        assert_eq!((err.offset, err.kind), (8, DecodeErrorKind::InvalidByte));
+        let err = error("Zg==\nZg==", options);
+        assert_eq!((err.offset, err.kind), (5, DecodeErrorKind::InvalidPadding));
+        let err = error("Zm9vY", options);
+        assert_eq!((err.offset, err.kind), (4, DecodeErrorKind::InvalidLength));
+        let err = error("Zm9vYg", options.padding(Padding::Required));
+        assert_eq!((err.offset, err.kind), (6, DecodeErrorKind::InvalidPadding));
+
+        let input = "Zg==\n \r\n";
+        let decoded = read_all(Base64Decoder::new(OneByte(input.as_bytes())));
+        assert_eq!(decoded.unwrap(), b"f");
+    }
+}
@@ -0,0 +1,453 @@
+//! `utils-rust` 命令行工具：对文件或标准输入进行 HTML 实体编码、解码，结果写到标准输出。
+
+use std::env;
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::process::{self, ExitCode};
+
+use utils_rust::ascii::{
+    DecodeOptions, EncodeMode, EncodeOptions, EncodeStyle, EntityDecoder, EntityEncoder,
+    ErrorPolicy,
+};
+
+const USAGE: &str = "\
+用法：
+    utils-rust encode [选项] [文件...]
+    utils-rust decode [选项] [文件...]
+
+未指定文件或文件为 `-` 时读取标准输入，结果写到标准输出。
+
+选项：
+    --style <格式>     编码格式：decimal（默认）、hex、hex-upper、named
+    --mode <范围>      需要编码的字符：all（默认）、html、non-ascii
+    --keep <字符>      只编码不在给定字符中的字符，优先于 --mode
+    --policy <策略>    无法解析的字符引用的处理方式：replace（默认）、strict、passthrough、drop
+    -i, --in-place     直接改写文件，而不是写到标准输出
+    -h, --help         显示帮助信息
+    --                 之后的参数都是文件，用于处理以 `-` 开头的文件名";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Encode,
+    Decode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    All,
+    HtmlSpecial,
+    NonAscii,
+}
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    command: Command,
+    style: EncodeStyle,
+    mode: Mode,
+    keep: Option<String>,
+    policy: ErrorPolicy,
+    in_place: bool,
+    files: Vec<String>,
+}
+
+impl Args {
+    fn encode_options(&self) -> EncodeOptions<'_> {
+        let mode = match (&self.keep, self.mode) {
+            (Some(keep), _) => EncodeMode::Except(keep),
+            (None, Mode::All) => EncodeMode::All,
+            (None, Mode::HtmlSpecial) => EncodeMode::HtmlSpecial,
+            (None, Mode::NonAscii) => EncodeMode::NonAscii,
+        };
+        EncodeOptions::new().mode(mode).style(self.style)
+    }
+
+    fn decode_options(&self) -> DecodeOptions {
+        DecodeOptions::new().policy(self.policy)
+    }
+}
+
+/// 解析命令行参数（不含程序名）；返回 `Ok(None)` 表示需要显示帮助信息。
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut args = args.into_iter();
+    let command = match args.next().as_deref() {
+        Some("encode") => Command::Encode,
+        Some("decode") => Command::Decode,
+        Some("-h" | "--help") | None => return Ok(None),
+        Some(other) => return Err(format!("未知的子命令 `{other}`")),
+    };
+    let mut parsed = Args {
+        command,
+        style: EncodeStyle::Decimal,
+        mode: Mode::All,
+        keep: None,
+        policy: ErrorPolicy::Replace,
+        in_place: false,
+        files: Vec::new(),
+    };
+
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                (flag.to_string(), Some(value.to_string()))
+            }
+            _ => (arg, None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("选项 `{flag}` 缺少参数"))
+        };
+        match flag.as_str() {
+            "--" => {
+                parsed.files.extend(args.by_ref());
+                break;
+            }
+            "-h" | "--help" => return Ok(None),
+            "-i" | "--in-place" => parsed.in_place = true,
+            "--style" => {
+                parsed.style = match value()?.as_str() {
+                    "decimal" => EncodeStyle::Decimal,
+                    "hex" => EncodeStyle::HexLower,
+                    "hex-upper" => EncodeStyle::HexUpper,
+                    "named" => EncodeStyle::Named,
+                    other => return Err(format!("未知的编码格式 `{other}`")),
+                }
+            }
+            "--mode" => {
+                parsed.mode = match value()?.as_str() {
+                    "all" => Mode::All,
+                    "html" => Mode::HtmlSpecial,
+                    "non-ascii" => Mode::NonAscii,
+                    other => return Err(format!("未知的编码范围 `{other}`")),
+                }
+            }
+            "--keep" => parsed.keep = Some(value()?),
+            "--policy" => {
+                parsed.policy = match value()?.as_str() {
+                    "strict" => ErrorPolicy::Strict,
+                    "replace" => ErrorPolicy::Replace,
+                    "passthrough" => ErrorPolicy::Passthrough,
+                    "drop" => ErrorPolicy::Drop,
+                    other => return Err(format!("未知的处理策略 `{other}`")),
+                }
+            }
+            "-" => parsed.files.push(flag),
+            _ if flag.starts_with('-') => return Err(format!("未知的选项 `{flag}`")),
+            _ => parsed.files.push(flag),
+        }
+    }
+
+    if parsed.in_place && (parsed.files.is_empty() || parsed.files.iter().any(|f| f == "-")) {
+        return Err("--in-place 需要指定文件，且不能读取标准输入".to_string());
+    }
+    Ok(Some(parsed))
+}
+
+/// 按子命令流式处理 `input`，结果写入 `output`。
+fn process(args: &Args, input: impl Read, mut output: impl Write) -> io::Result<()> {
+    match args.command {
+        Command::Encode => {
+            let mut encoder = EntityEncoder::with_options(output, args.encode_options());
+            io::copy(&mut BufReader::new(input), &mut encoder)?;
+            encoder.finish()?.flush()
+        }
+        Command::Decode => {
+            let mut decoder = EntityDecoder::with_options(input, args.decode_options());
+            io::copy(&mut decoder, &mut output)?;
+            output.flush()
+        }
+    }
+}
+
+/// 创建临时文件的最大尝试次数。
+const TEMP_ATTEMPTS: u32 = 100;
+
+/// 在 `path` 所在目录下创建新的临时文件，返回其路径和句柄。
+///
+/// 文件名带有进程号和序号，只创建原本不存在的文件：同名文件或符号链接已存在时换下一个序号，不会打开、截断或删除它。
+fn create_temp(path: &Path) -> io::Result<(OsString, File)> {
+    let mut attempt = 0;
+    loop {
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(format!(".utils-rust.{}.{attempt}.tmp", process::id()));
+        match OpenOptions::new().write(true).create_new(true).open(&tmp) {
+            Ok(out) => return Ok((tmp, out)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists && attempt + 1 < TEMP_ATTEMPTS => {
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// 处理文件并原地改写：先写入同目录下新建的临时文件，成功后再替换原文件。
+///
+/// 临时文件在写入前复制原文件的权限，替换后原文件的权限保持不变；任何一步出错时删除本次创建的临时文件。
+fn rewrite(args: &Args, file: &str) -> io::Result<()> {
+    let path = Path::new(file);
+    let input = File::open(path)?;
+    let permissions = input.metadata()?.permissions();
+    let (tmp, out) = create_temp(path)?;
+    let result = out
+        .set_permissions(permissions)
+        .and_then(|()| process(args, input, BufWriter::new(out)))
+        .and_then(|()| fs::rename(&tmp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// 为 I/O 错误加上文件名。
+fn context(file: &str) -> impl FnOnce(io::Error) -> String + '_ {
+    move |e| format!("{file}: {e}")
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    if args.in_place {
+        for file in &args.files {
+            rewrite(args, file).map_err(context(file))?;
+        }
+        return Ok(());
+    }
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    if args.files.is_empty() {
+        return process(args, io::stdin().lock(), &mut out).map_err(context("<stdin>"));
+    }
+    for file in &args.files {
+        let result = match file.as_str() {
+            "-" => process(args, io::stdin().lock(), &mut out),
+            _ => File::open(file).and_then(|input| process(args, input, &mut out)),
+        };
+        result.map_err(context(file))?;
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("utils-rust: {e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("utils-rust: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<Args>, String> {
+        parse_args(args.iter().map(|s| s.to_string()))
+    }
+
+    fn transform(args: &[&str], input: &str) -> io::Result<String> {
+        let args = parse(args).unwrap().unwrap();
+        let mut out = Vec::new();
+        process(&args, input.as_bytes(), &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = parse(&[
+            "encode",
+            "--style=hex",
+            "--mode",
+            "html",
+            "-i",
+            "a.html",
+            "b.html",
+        ])
+        .unwrap()
+        .unwrap();
+        assert_eq!(args.command, Command::Encode);
+        assert_eq!(args.style, EncodeStyle::HexLower);
+        assert_eq!(args.mode, Mode::HtmlSpecial);
+        assert!(args.in_place);
+        assert_eq!(args.files, ["a.html", "b.html"]);
+
+        let args = parse(&["decode", "--policy", "strict", "-"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(args.policy, ErrorPolicy::Strict);
+        assert_eq!(args.files, ["-"]);
+
+        assert_eq!(parse(&[]), Ok(None));
+        assert_eq!(parse(&["decode", "--help"]), Ok(None));
+        assert!(parse(&["convert"]).is_err());
+        assert!(parse(&["encode", "--style"]).is_err());
+        assert!(parse(&["encode", "--style", "octal"]).is_err());
+        assert!(parse(&["encode", "--bogus"]).is_err());
+        assert!(parse(&["encode", "-i"]).is_err());
+        assert!(parse(&["encode", "-i", "-"]).is_err());
+
+        let args = parse(&["encode", "-i", "--", "-i", "--style"])
+            .unwrap()
+            .unwrap();
+        assert!(args.in_place);
+        assert_eq!(args.style, EncodeStyle::Decimal);
+        assert_eq!(args.files, ["-i", "--style"]);
+        let args = parse(&["encode", "--", "--help", "-"]).unwrap().unwrap();
+        assert_eq!(args.files, ["--help", "-"]);
+        let args = parse(&["decode", "--"]).unwrap().unwrap();
+        assert!(args.files.is_empty());
+    }
+
+    #[test]
+    fn test_process() {
+        assert_eq!(transform(&["encode"], "测试").unwrap(), "&#27979;&#35797;");
+        assert_eq!(
+            transform(&["encode", "--mode", "html", "--style", "named"], "<测> ©").unwrap(),
+            "&lt;测&gt; ©"
+        );
+        assert_eq!(
+            transform(&["encode", "--keep", "ab", "--style", "hex-upper"], "abc").unwrap(),
+            "ab&#x63;"
+        );
+        assert_eq!(
+            transform(&["decode"], "&#27979;&#x8BD5; &amp; &#0;").unwrap(),
+            "测试 & \u{FFFD}"
+        );
+        assert_eq!(
+            transform(&["decode", "--policy", "passthrough"], "&#0;").unwrap(),
+            "&#0;"
+        );
+        let err = transform(&["decode", "--policy", "strict"], "ok &#0;").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_rewrite() {
+        let dir = env::temp_dir().join(format!("utils-rust-cli-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("page.html");
+        fs::write(&file, "&lt;p&gt;测试&lt;/p&gt;").unwrap();
+
+        let path = file.to_str().unwrap();
+        let args = parse(&["decode", "--in-place", path]).unwrap().unwrap();
+        run(&args).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "<p>测试</p>");
+
+        // 出错时原文件保持不变，临时文件被删除。
+        let args = parse(&["decode", "-i", "--policy", "strict", path])
+            .unwrap()
+            .unwrap();
+        fs::write(&file, "&lt;p&gt; &#0;").unwrap();
+        assert!(run(&args).is_err());
+        assert_eq!(fs::read_to_string(&file).unwrap(), "&lt;p&gt; &#0;");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        let args = parse(&["decode", "-i", dir.join("missing.html").to_str().unwrap()])
+            .unwrap()
+            .unwrap();
+        assert!(run(&args).is_err());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_rewrite_existing_temp() {
+        let dir = env::temp_dir().join(format!("utils-rust-cli-temp-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("page.html");
+        fs::write(&file, "&lt;p&gt;").unwrap();
+
+        // 与临时文件同名的已有文件不会被截断或删除。
+        let taken = [
+            dir.join("page.html.utils-rust.tmp"),
+            dir.join(format!("page.html.utils-rust.{}.0.tmp", process::id())),
+        ];
+        for other in &taken {
+            fs::write(other, "keep").unwrap();
+        }
+        let path = file.to_str().unwrap();
+        let args = parse(&["decode", "-i", path]).unwrap().unwrap();
+        run(&args).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "<p>");
+
+        let args = parse(&["decode", "-i", "--policy", "strict", path])
+            .unwrap()
+            .unwrap();
+        fs::write(&file, "&#0;").unwrap();
+        assert!(run(&args).is_err());
+        assert_eq!(fs::read_to_string(&file).unwrap(), "&#0;");
+        for other in &taken {
+            assert_eq!(fs::read_to_string(other).unwrap(), "keep");
+        }
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 3);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rewrite_temp_symlink() {
+        use std::os::unix::fs::symlink;
+
+        let dir = env::temp_dir().join(format!("utils-rust-cli-link-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("page.html");
+        fs::write(&file, "&lt;p&gt;").unwrap();
+        let victim = dir.join("victim");
+        fs::write(&victim, "keep").unwrap();
+        let link = dir.join(format!("page.html.utils-rust.{}.0.tmp", process::id()));
+        symlink(&victim, &link).unwrap();
+
+        // 不跟随临时文件名处的符号链接，失败时也不删除它。
+        let path = file.to_str().unwrap();
+        let args = parse(&["decode", "-i", path]).unwrap().unwrap();
+        run(&args).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "<p>");
+
+        let args = parse(&["decode", "-i", "--policy", "strict", path])
+            .unwrap()
+            .unwrap();
+        fs::write(&file, "&#0;").unwrap();
+        assert!(run(&args).is_err());
+        assert_eq!(fs::read_to_string(&file).unwrap(), "&#0;");
+        assert_eq!(fs::read_to_string(&victim).unwrap(), "keep");
+        assert!(fs::symlink_metadata(&link).unwrap().is_symlink());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rewrite_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = env::temp_dir().join(format!("utils-rust-cli-perm-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for mode in [0o755, 0o600, 0o444] {
+            let file = dir.join("-script");
+            fs::write(&file, "echo &quot;ok&quot;").unwrap();
+            fs::set_permissions(&file, fs::Permissions::from_mode(mode)).unwrap();
+
+            let args = parse(&["decode", "-i", "--", file.to_str().unwrap()])
+                .unwrap()
+                .unwrap();
+            run(&args).unwrap();
+            assert_eq!(fs::read_to_string(&file).unwrap(), "echo \"ok\"");
+            let metadata = fs::metadata(&file).unwrap();
+            assert_eq!(metadata.permissions().mode() & 0o777, mode);
+            fs::remove_file(&file).unwrap();
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
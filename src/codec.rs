@@ -0,0 +1,497 @@
+use alloc::borrow::{Cow, ToOwned};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::error::Error;
+use core::fmt;
+
+use crate::ascii::{DecodeOptions, EncodeOptions};
+use crate::{base32, base64, hex, percent, unicode};
+
+/// 类型擦除后的错误，用于运行时选择的编解码器。
+pub type BoxError = Box<dyn Error + Send + Sync>;
+
+/// 类型擦除后的编码器，由 [`encoder`] 返回。
+pub type DynEncoder = Box<dyn Encoder<Error = BoxError> + Send + Sync>;
+
+/// 类型擦除后的解码器，由 [`decoder`] 返回。
+pub type DynDecoder = Box<dyn Decoder<Error = BoxError> + Send + Sync>;
+
+/// 可按名称选择的编解码器，见 [`encoder`] 和 [`decoder`]。
+pub const CODECS: &[&str] = &[
+    "html",
+    "unicode",
+    "percent",
+    "base64",
+    "base64url",
+    "base32",
+    "base32hex",
+    "hex",
+];
+
+/// 解码时遇到无法解析的内容（如 HTML 中码点不合法的字符引用、`\u` 转义中没有配对的代理项、百分号编码解码后不是 UTF-8 的字节）时的处理方式。
+///
+/// # 说明
+///
+/// [`ascii`](crate::ascii)、[`unicode`](crate::unicode)、[`percent`](crate::percent) 的 `DecodeOptions::policy` 共用这个枚举，哪些内容视为无法解析、格式不正确的内容在非严格模式下如何处理，见各模块 `DecodeOptions::policy` 的说明。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// 返回解码错误，错误中包含出错位置和原因；格式不正确的内容同样视为错误。
+    Strict,
+    /// 替换为 U+FFFD（`�`）。
+    #[default]
+    Replace,
+    /// 保留出错内容的原文。
+    Passthrough,
+    /// 删除出错的内容。
+    Drop,
+}
+
+/// 编码器：将文本或字节转换为编码后的形式。
+///
+/// # 说明
+///
+/// 每种编码器同时提供文本和字节两种输入方式，错误类型由 [`Encoder::Error`] 指定；不会失败的方法同样返回 `Result`，便于泛型代码统一处理。输入无需改变时可以直接借用输入。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::ascii::{EncodeMode, EncodeOptions};
+/// # use utils_rust::Encoder;
+/// fn encode_all<E: Encoder>(encoder: &E, items: &[&str]) -> Result<Vec<String>, E::Error> {
+///     items
+///         .iter()
+///         .map(|s| encoder.encode_str(s).map(|s| s.into_owned()))
+///         .collect()
+/// }
+///
+/// let encoder = EncodeOptions::new().mode(EncodeMode::HtmlSpecial);
+/// assert_eq!(encode_all(&encoder, &["<a>", "b"]).unwrap(), ["&#60;a&#62;", "b"]);
+/// ```
+pub trait Encoder {
+    /// 编码失败时返回的错误。
+    type Error;
+
+    /// 编码文本。
+    fn encode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, Self::Error>;
+
+    /// 编码字节。
+    fn encode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, Self::Error>;
+
+    /// 先用当前编码器编码，再将结果交给 `next` 编码。
+    fn then<E: Encoder>(self, next: E) -> Chain<Self, E>
+    where
+        Self: Sized,
+    {
+        Chain {
+            first: self,
+            second: next,
+        }
+    }
+
+    /// 擦除具体类型，错误统一转换为 [`BoxError`]。
+    fn boxed(self) -> DynEncoder
+    where
+        Self: Sized + Send + Sync + 'static,
+        Self::Error: Error + Send + Sync + 'static,
+    {
+        Box::new(Boxed(self))
+    }
+}
+
+/// 解码器：将编码后的文本或字节还原。
+///
+/// # 说明
+///
+/// 与 [`Encoder`] 对应，同时提供文本和字节两种输入方式。链式解码时注意顺序与编码相反。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::ascii::{DecodeOptions, ErrorPolicy};
+/// # use utils_rust::Decoder;
+/// let decoder = DecodeOptions::new().policy(ErrorPolicy::Strict);
+/// assert_eq!(decoder.decode_str("&amp;lt;").unwrap(), "&lt;");
+/// assert_eq!(decoder.then(decoder).decode_str("&amp;lt;").unwrap(), "<");
+/// assert!(decoder.decode_bytes(b"&#0;").is_err());
+/// ```
+pub trait Decoder {
+    /// 解码失败时返回的错误。
+    type Error;
+
+    /// 解码文本。
+    ///
+    /// Base64、Base32、十六进制等二进制编码的解码结果必须是合法的 UTF-8，否则返回错误，错误偏移为解码结果中第一个非法字节的位置。
+    fn decode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, Self::Error>;
+
+    /// 解码字节。
+    fn decode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, Self::Error>;
+
+    /// 先用当前解码器解码，再将结果交给 `next` 解码。
+    fn then<D: Decoder>(self, next: D) -> Chain<Self, D>
+    where
+        Self: Sized,
+    {
+        Chain {
+            first: self,
+            second: next,
+        }
+    }
+
+    /// 擦除具体类型，错误统一转换为 [`BoxError`]。
+    fn boxed(self) -> DynDecoder
+    where
+        Self: Sized + Send + Sync + 'static,
+        Self::Error: Error + Send + Sync + 'static,
+    {
+        Box::new(Boxed(self))
+    }
+}
+
+impl<T: Encoder + ?Sized> Encoder for &T {
+    type Error = T::Error;
+
+    fn encode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, Self::Error> {
+        (**self).encode_str(input)
+    }
+
+    fn encode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, Self::Error> {
+        (**self).encode_bytes(input)
+    }
+}
+
+impl<T: Encoder + ?Sized> Encoder for Box<T> {
+    type Error = T::Error;
+
+    fn encode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, Self::Error> {
+        (**self).encode_str(input)
+    }
+
+    fn encode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, Self::Error> {
+        (**self).encode_bytes(input)
+    }
+}
+
+impl<T: Decoder + ?Sized> Decoder for &T {
+    type Error = T::Error;
+
+    fn decode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, Self::Error> {
+        (**self).decode_str(input)
+    }
+
+    fn decode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, Self::Error> {
+        (**self).decode_bytes(input)
+    }
+}
+
+impl<T: Decoder + ?Sized> Decoder for Box<T> {
+    type Error = T::Error;
+
+    fn decode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, Self::Error> {
+        (**self).decode_str(input)
+    }
+
+    fn decode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, Self::Error> {
+        (**self).decode_bytes(input)
+    }
+}
+
+/// 依次应用两个编码器或解码器，由 [`Encoder::then`] 和 [`Decoder::then`] 创建。
+#[derive(Debug, Clone, Copy)]
+pub struct Chain<A, B> {
+    first: A,
+    second: B,
+}
+
+/// [`Chain`] 中某一步返回的错误。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainError<A, B> {
+    /// 第一步失败。
+    First(A),
+    /// 第二步失败。
+    Second(B),
+}
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Display for ChainError<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainError::First(e) => e.fmt(f),
+            ChainError::Second(e) => e.fmt(f),
+        }
+    }
+}
+
+impl<A, B> Error for ChainError<A, B>
+where
+    A: fmt::Debug + fmt::Display,
+    B: fmt::Debug + fmt::Display,
+{
+}
+
+/// 将二进制解码结果作为文本返回，规则见 [`Decoder::decode_str`]；`error` 根据偏移构造各模块的错误。
+pub(crate) fn decoded_str<E>(
+    bytes: Vec<u8>,
+    error: impl FnOnce(usize) -> E,
+) -> Result<Cow<'static, str>, E> {
+    String::from_utf8(bytes)
+        .map(Cow::Owned)
+        .map_err(|e| error(e.utf8_error().valid_up_to()))
+}
+
+/// 将第一步的结果交给第二步；第一步借用输入时，第二步的结果同样可以借用输入。
+fn chain<'a, T, A, B>(
+    first: Result<Cow<'a, T>, A>,
+    second: impl for<'b> Fn(&'b T) -> Result<Cow<'b, T>, B>,
+) -> Result<Cow<'a, T>, ChainError<A, B>>
+where
+    T: ToOwned + ?Sized,
+{
+    match first.map_err(ChainError::First)? {
+        Cow::Borrowed(s) => second(s).map_err(ChainError::Second),
+        Cow::Owned(s) => second(s.borrow())
+            .map(|r| Cow::Owned(r.into_owned()))
+            .map_err(ChainError::Second),
+    }
+}
+
+impl<A: Encoder, B: Encoder> Encoder for Chain<A, B> {
+    type Error = ChainError<A::Error, B::Error>;
+
+    fn encode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, Self::Error> {
+        chain(self.first.encode_str(input), |s| self.second.encode_str(s))
+    }
+
+    fn encode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, Self::Error> {
+        chain(self.first.encode_bytes(input), |s| {
+            self.second.encode_bytes(s)
+        })
+    }
+}
+
+impl<A: Decoder, B: Decoder> Decoder for Chain<A, B> {
+    type Error = ChainError<A::Error, B::Error>;
+
+    fn decode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, Self::Error> {
+        chain(self.first.decode_str(input), |s| self.second.decode_str(s))
+    }
+
+    fn decode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, Self::Error> {
+        chain(self.first.decode_bytes(input), |s| {
+            self.second.decode_bytes(s)
+        })
+    }
+}
+
+/// 将错误转换为 [`BoxError`] 的包装，由 `boxed` 创建。
+struct Boxed<T>(T);
+
+impl<T> Encoder for Boxed<T>
+where
+    T: Encoder,
+    T::Error: Error + Send + Sync + 'static,
+{
+    type Error = BoxError;
+
+    fn encode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, BoxError> {
+        Ok(self.0.encode_str(input)?)
+    }
+
+    fn encode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, BoxError> {
+        Ok(self.0.encode_bytes(input)?)
+    }
+}
+
+impl<T> Decoder for Boxed<T>
+where
+    T: Decoder,
+    T::Error: Error + Send + Sync + 'static,
+{
+    type Error = BoxError;
+
+    fn decode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, BoxError> {
+        Ok(self.0.decode_str(input)?)
+    }
+
+    fn decode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, BoxError> {
+        Ok(self.0.decode_bytes(input)?)
+    }
+}
+
+/// 按名称获取编码器，名称不存在时返回 `None`。
+///
+/// # 说明
+///
+/// 可用的名称见 [`CODECS`]，每种编解码器使用各自的默认选项：
+///
+/// - `html`：HTML 实体编码，等同于 [`EncodeOptions::new`]。
+/// - `unicode`：JavaScript、JSON 的 `\uXXXX` 转义，等同于 [`unicode::EncodeOptions::new`]。
+/// - `percent`：URL 的百分号编码，等同于 [`percent::EncodeOptions::new`]。
+/// - `base64`：标准的 Base64，等同于 [`base64::EncodeOptions::new`]。
+/// - `base64url`：URL 安全的 Base64，不带填充，常见于 JWT。
+/// - `base32`：标准的 Base32，等同于 [`base32::EncodeOptions::new`]。
+/// - `base32hex`：扩展十六进制字母表的 Base32。
+/// - `hex`：小写的十六进制，等同于 [`hex::EncodeOptions::new`]。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::{encoder, Encoder};
+/// let html = encoder("html").unwrap();
+/// assert_eq!(html.encode_str("测").unwrap(), "&#27979;");
+/// assert!(encoder("rot13").is_none());
+///
+/// // 按配置中的名称列表组合编码器。
+/// let twice = ["html", "html"]
+///     .iter()
+///     .map(|name| encoder(name).unwrap())
+///     .reduce(|a, b| a.then(b).boxed())
+///     .unwrap();
+/// assert_eq!(twice.encode_str("<").unwrap(), "&#38;&#35;&#54;&#48;&#59;");
+/// ```
+pub fn encoder(name: &str) -> Option<DynEncoder> {
+    match name {
+        "html" => Some(EncodeOptions::new().boxed()),
+        "unicode" => Some(unicode::EncodeOptions::new().boxed()),
+        "percent" => Some(percent::EncodeOptions::new().boxed()),
+        "base64" => Some(base64::EncodeOptions::new().boxed()),
+        "base64url" => Some(
+            base64::EncodeOptions::new()
+                .alphabet(base64::Alphabet::UrlSafe)
+                .padding(false)
+                .boxed(),
+        ),
+        "base32" => Some(base32::EncodeOptions::new().boxed()),
+        "base32hex" => Some(
+            base32::EncodeOptions::new()
+                .alphabet(base32::Alphabet::Hex)
+                .boxed(),
+        ),
+        "hex" => Some(hex::EncodeOptions::new().boxed()),
+        _ => None,
+    }
+}
+
+/// 按名称获取解码器，名称不存在时返回 `None`。
+///
+/// # 说明
+///
+/// 可用的名称见 [`CODECS`]，每种编解码器使用各自的默认选项：
+///
+/// - `html`：HTML 实体编码，等同于 [`DecodeOptions::new`]。
+/// - `unicode`：JavaScript、JSON 的 `\uXXXX` 转义，等同于 [`unicode::DecodeOptions::new`]。
+/// - `percent`：URL 的百分号编码，等同于 [`percent::DecodeOptions::new`]。
+/// - `base64`：标准的 Base64，等同于 [`base64::DecodeOptions::new`]。
+/// - `base64url`：URL 安全的 Base64，有没有填充都可以。
+/// - `base32`：标准的 Base32，等同于 [`base32::DecodeOptions::new`]。
+/// - `base32hex`：扩展十六进制字母表的 Base32。
+/// - `hex`：十六进制，大小写均可，等同于 [`hex::DecodeOptions::new`]。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::{decoder, Decoder};
+/// let html = decoder("html").unwrap();
+/// assert_eq!(html.decode_bytes(b"&#27979;").unwrap(), "测".as_bytes());
+/// ```
+pub fn decoder(name: &str) -> Option<DynDecoder> {
+    match name {
+        "html" => Some(DecodeOptions::new().boxed()),
+        "unicode" => Some(unicode::DecodeOptions::new().boxed()),
+        "percent" => Some(percent::DecodeOptions::new().boxed()),
+        "base64" => Some(base64::DecodeOptions::new().boxed()),
+        "base64url" => Some(
+            base64::DecodeOptions::new()
+                .alphabet(base64::Alphabet::UrlSafe)
+                .boxed(),
+        ),
+        "base32" => Some(base32::DecodeOptions::new().boxed()),
+        "base32hex" => Some(
+            base32::DecodeOptions::new()
+                .alphabet(base32::Alphabet::Hex)
+                .boxed(),
+        ),
+        "hex" => Some(hex::DecodeOptions::new().boxed()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::ascii::{DecodeErrorKind, EncodeMode, ErrorPolicy, Utf8Policy};
+
+    #[test]
+    fn test_ascii_codec() {
+        let encoder = EncodeOptions::new().mode(EncodeMode::HtmlSpecial);
+        assert!(matches!(encoder.encode_str("plain"), Ok(Cow::Borrowed(_))));
+        assert_eq!(
+            encoder.encode_bytes(b"<\xff").unwrap(),
+            "&#60;\u{FFFD}".as_bytes()
+        );
+        assert!(encoder
+            .utf8(Utf8Policy::Strict)
+            .encode_bytes(b"\xff")
+            .is_err());
+
+        let decoder = DecodeOptions::new().policy(ErrorPolicy::Strict);
+        assert_eq!(decoder.decode_str("&lt;").unwrap(), "<");
+        let err = decoder.decode_bytes(b"&#0;").unwrap_err();
+        assert_eq!(err.kind, DecodeErrorKind::NullCharacter);
+    }
+
+    #[test]
+    fn test_chain() {
+        let special = EncodeOptions::new().mode(EncodeMode::HtmlSpecial);
+        let chained = special.then(special);
+        assert!(matches!(chained.encode_str("plain"), Ok(Cow::Borrowed(_))));
+        assert_eq!(chained.encode_str("<").unwrap(), "&#38;#60;");
+        assert_eq!(chained.encode_bytes(b"<").unwrap(), &b"&#38;#60;"[..]);
+
+        let decoder = DecodeOptions::new().policy(ErrorPolicy::Strict);
+        let chained = decoder.then(decoder);
+        assert_eq!(chained.decode_str("&#38;#60;").unwrap(), "<");
+        assert!(matches!(
+            chained.decode_str("&#0;"),
+            Err(ChainError::First(_))
+        ));
+        assert!(matches!(
+            chained.decode_str("&amp;#0;"),
+            Err(ChainError::Second(_))
+        ));
+    }
+
+    #[test]
+    fn test_registry() {
+        for name in CODECS {
+            let encoded = encoder(name)
+                .unwrap()
+                .encode_str("<测试>")
+                .unwrap()
+                .into_owned();
+            let decoded = decoder(name)
+                .unwrap()
+                .decode_str(&encoded)
+                .unwrap()
+                .into_owned();
+            assert_eq!(decoded, "<测试>", "{name}");
+        }
+        assert!(encoder("unknown").is_none());
+        assert!(decoder("").is_none());
+
+        let strict = DecodeOptions::new().policy(ErrorPolicy::Strict).boxed();
+        let chained = decoder("html").unwrap().then(strict).boxed();
+        let err = chained.decode_str("&amp;#0;").unwrap_err();
+        assert_eq!(err.to_string(), strict_error("&#0;"));
+    }
+
+    fn strict_error(input: &str) -> alloc::string::String {
+        DecodeOptions::new()
+            .policy(ErrorPolicy::Strict)
+            .decode(input)
+            .unwrap_err()
+            .to_string()
+    }
+}
@@ -0,0 +1,186 @@
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use super::digits;
+
+/// 十六进制转储（hexdump）的格式选项。
+///
+/// # 说明
+///
+/// 默认格式与 `xxd` 相同：每行 16 字节，每 2 字节一组，行首是 8 位十六进制的偏移，行尾是 ASCII 栏，其中可打印字符原样显示，其余字节显示为 `.`。最后一行不足一行时用空格补齐，使 ASCII 栏对齐。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::hex::DumpOptions;
+/// let options = DumpOptions::new().width(8).group(1).offset(0x100);
+/// assert_eq!(
+///     options.dump("GET /\r\n"),
+///     "00000100: 47 45 54 20 2f 0d 0a     GET /..\n",
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DumpOptions {
+    pub(crate) width: usize,
+    pub(crate) group: usize,
+    pub(crate) uppercase: bool,
+    pub(crate) offset: usize,
+}
+
+impl Default for DumpOptions {
+    fn default() -> Self {
+        Self {
+            width: 16,
+            group: 2,
+            uppercase: false,
+            offset: 0,
+        }
+    }
+}
+
+impl DumpOptions {
+    /// 创建默认的格式选项。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置每行的字节数，默认 16，为 0 时按 1 处理。
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width.max(1);
+        self
+    }
+
+    /// 设置每组的字节数，组之间用一个空格分隔，默认 2；为 0 时不分组。
+    pub fn group(mut self, group: usize) -> Self {
+        self.group = group;
+        self
+    }
+
+    /// 设置十六进制数字是否使用大写字母，默认使用小写；行首的偏移始终使用小写。
+    pub fn uppercase(mut self, uppercase: bool) -> Self {
+        self.uppercase = uppercase;
+        self
+    }
+
+    /// 设置第一个字节显示的偏移，默认 0，便于转储较大数据中的一段；之后各行的偏移超出 `usize` 范围时从 0 回绕。
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// 按当前选项生成转储文本，每行以 `\n` 结尾；输入为空时返回空字符串。
+    pub fn dump(&self, input: impl AsRef<[u8]>) -> String {
+        let mut out = String::new();
+        let _ = dump_into(input.as_ref(), self, &mut out);
+        out
+    }
+
+    /// 按当前选项生成转储文本并写入 `out`。
+    pub fn dump_to<W: Write + ?Sized>(&self, input: impl AsRef<[u8]>, out: &mut W) -> fmt::Result {
+        dump_into(input.as_ref(), self, out)
+    }
+}
+
+/// 转储的核心实现，将结果写入 `out`。
+pub(crate) fn dump_into<W: Write + ?Sized>(
+    bytes: &[u8],
+    options: &DumpOptions,
+    out: &mut W,
+) -> fmt::Result {
+    let digits = digits(options.uppercase);
+    for (line, chunk) in bytes.chunks(options.width).enumerate() {
+        // 偏移超出 `usize` 的范围时回绕，见 `DumpOptions::offset`。
+        let offset = options
+            .offset
+            .wrapping_add(line.wrapping_mul(options.width));
+        write!(out, "{offset:08x}: ")?;
+        for i in 0..options.width {
+            if i > 0 && options.group > 0 && i % options.group == 0 {
+                out.write_char(' ')?;
+            }
+            match chunk.get(i) {
+                Some(&b) => {
+                    out.write_char(char::from(digits[usize::from(b >> 4)]))?;
+                    out.write_char(char::from(digits[usize::from(b & 0xF)]))?;
+                }
+                None => out.write_str("  ")?,
+            }
+        }
+        out.write_str("  ")?;
+        for &b in chunk {
+            out.write_char(match b {
+                0x20..=0x7E => char::from(b),
+                _ => '.',
+            })?;
+        }
+        out.write_char('\n')?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xxd() {
+        // 以下结果均与 xxd 的输出逐字节一致。
+        assert_eq!(
+            DumpOptions::new().dump("Hello, world!\n\x00\x01\u{7f}"),
+            concat!(
+                "00000000: 4865 6c6c 6f2c 2077 6f72 6c64 210a 0001  Hello, world!...\n",
+                "00000010: 7f                                       .\n",
+            )
+        );
+        assert_eq!(
+            DumpOptions::new().group(1).dump("hello\n"),
+            "00000000: 68 65 6c 6c 6f 0a                                hello.\n"
+        );
+        assert_eq!(
+            DumpOptions::new().group(0).uppercase(true).dump("hello\n"),
+            "00000000: 68656C6C6F0A                      hello.\n"
+        );
+        assert_eq!(
+            DumpOptions::new()
+                .width(8)
+                .group(3)
+                .dump("hello world 1234"),
+            concat!(
+                "00000000: 68656c 6c6f20 776f  hello wo\n",
+                "00000008: 726c64 203132 3334  rld 1234\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_options() {
+        assert_eq!(DumpOptions::new().dump(""), "");
+        assert_eq!(
+            DumpOptions::new().width(0).offset(0xABC).dump("ab"),
+            "00000abc: 61  a\n00000abd: 62  b\n"
+        );
+        assert_eq!(
+            DumpOptions::new()
+                .width(4)
+                .group(8)
+                .uppercase(true)
+                .dump([0xff, 0x7e, 0x20]),
+            "00000000: FF7E20    .~ \n"
+        );
+        let max = format!("{:x}", usize::MAX);
+        assert_eq!(
+            DumpOptions::new().offset(usize::MAX).dump([0x41; 17]),
+            format!(
+                "{max}: 4141 4141 4141 4141 4141 4141 4141 4141  AAAAAAAAAAAAAAAA\n\
+                 0000000f: 41                                       A\n"
+            )
+        );
+
+        let mut out = String::from("> ");
+        DumpOptions::new()
+            .width(2)
+            .dump_to([0u8; 3], &mut out)
+            .unwrap();
+        assert_eq!(out, "> 00000000: 0000  ..\n00000002: 00    .\n");
+    }
+}
@@ -0,0 +1,49 @@
+use core::error::Error;
+use core::fmt;
+
+/// 十六进制解析失败的原因。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeErrorKind {
+    /// 不是十六进制数字，也不是可以忽略的分隔符或空白字符。
+    InvalidByte,
+    /// 某个字节只有一个数字：后面紧跟分隔符、空白字符或输入已结束；此时偏移为该数字的位置。
+    InvalidLength,
+    /// 解码结果不是有效的 UTF-8，见 [`Decoder::decode_str`](crate::Decoder::decode_str)。
+    InvalidUtf8,
+}
+
+impl fmt::Display for DecodeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DecodeErrorKind::InvalidByte => "不是十六进制数字",
+            DecodeErrorKind::InvalidLength => "缺少一个十六进制数字",
+            DecodeErrorKind::InvalidUtf8 => "解码结果不是有效的 UTF-8",
+        })
+    }
+}
+
+/// 解码十六进制文本时遇到的错误。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::hex::{decode, DecodeErrorKind};
+/// let err = decode("de ad b").unwrap_err();
+/// assert_eq!(err.offset, 6);
+/// assert_eq!(err.kind, DecodeErrorKind::InvalidLength);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeError {
+    /// 出错位置在输入中的字节偏移。
+    pub offset: usize,
+    /// 出错原因。
+    pub kind: DecodeErrorKind,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "第 {} 字节处的十六进制无效：{}", self.offset, self.kind)
+    }
+}
+
+impl Error for DecodeError {}
@@ -0,0 +1,284 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+use core::str;
+
+mod dump;
+mod error;
+mod options;
+
+pub use dump::DumpOptions;
+pub use error::{DecodeError, DecodeErrorKind};
+pub use options::{DecodeOptions, EncodeOptions};
+
+use dump::dump_into;
+
+/// 编码时使用的缓冲区大小。
+const BUF_SIZE: usize = 1024;
+
+/// 十六进制数字，按 `uppercase` 选择大小写。
+fn digits(uppercase: bool) -> &'static [u8; 16] {
+    match uppercase {
+        true => b"0123456789ABCDEF",
+        false => b"0123456789abcdef",
+    }
+}
+
+/// 编码的核心实现，将结果写入 `out`。
+fn encode_into<W: Write + ?Sized>(
+    bytes: &[u8],
+    options: &EncodeOptions,
+    out: &mut W,
+) -> fmt::Result {
+    let digits = digits(options.uppercase);
+    let mut sep = [0; 4];
+    let sep = options.separator.map_or("", |c| c.encode_utf8(&mut sep));
+    let mut buf = [0; BUF_SIZE];
+    let mut n = 0;
+    for (i, &b) in bytes.iter().enumerate() {
+        // 每个字节最多占用 2 个数字和 4 字节的分隔符。
+        if n + 6 > BUF_SIZE {
+            out.write_str(str::from_utf8(&buf[..n]).unwrap_or_default())?;
+            n = 0;
+        }
+        if i > 0 {
+            buf[n..n + sep.len()].copy_from_slice(sep.as_bytes());
+            n += sep.len();
+        }
+        buf[n] = digits[usize::from(b >> 4)];
+        buf[n + 1] = digits[usize::from(b & 0xF)];
+        n += 2;
+    }
+    // 缓冲区中只有完整的字符。
+    out.write_str(str::from_utf8(&buf[..n]).unwrap_or_default())
+}
+
+/// 解码的核心实现，将结果追加到 `out`。
+fn decode_into(
+    input: &[u8],
+    options: &DecodeOptions,
+    out: &mut Vec<u8>,
+) -> Result<(), DecodeError> {
+    let error = |offset, kind| Err(DecodeError { offset, kind });
+    let mut sep = [0; 4];
+    let sep = options.separator.map_or("", |c| c.encode_utf8(&mut sep));
+    // 已读取的高位数字及其位置。
+    let mut high: Option<(usize, u8)> = None;
+    let mut i = 0;
+    while i < input.len() {
+        let b = input[i];
+        let skip = if options.whitespace && b.is_ascii_whitespace() {
+            1
+        } else if !sep.is_empty() && input[i..].starts_with(sep.as_bytes()) {
+            sep.len()
+        } else {
+            0
+        };
+        if skip > 0 {
+            // 分隔符和空白字符只能出现在字节之间，出现在一对数字中间说明前一个字节只有一个数字。
+            if let Some((j, _)) = high {
+                return error(j, DecodeErrorKind::InvalidLength);
+            }
+            i += skip;
+            continue;
+        }
+        let Some(value) = char::from(b).to_digit(16) else {
+            return error(i, DecodeErrorKind::InvalidByte);
+        };
+        match high.take() {
+            Some((_, h)) => out.push(h << 4 | value as u8),
+            None => high = Some((i, value as u8)),
+        }
+        i += 1;
+    }
+    match high {
+        Some((i, _)) => error(i, DecodeErrorKind::InvalidLength),
+        None => Ok(()),
+    }
+}
+
+/// 将字节编码为小写的十六进制文本。
+///
+/// # 说明
+///
+/// 输入可以是任意字节，也可以是字符串（按 UTF-8 字节编码）。需要大写字母或 `:`、空格等分隔符时，请使用 [`EncodeOptions`]。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::hex::encode;
+/// assert_eq!(encode("测"), "e6b58b");
+/// assert_eq!(encode([0xde, 0xad, 0xbe, 0xef]), "deadbeef");
+/// ```
+pub fn encode(input: impl AsRef<[u8]>) -> String {
+    EncodeOptions::new().encode(input)
+}
+
+/// 将字节编码为小写的十六进制文本并写入 `out`，不分配中间字符串。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::hex::encode_to;
+/// let mut etag = String::from("W/\"");
+/// encode_to([0x1f, 0x2e], &mut etag).unwrap();
+/// etag.push('"');
+/// assert_eq!(etag, "W/\"1f2e\"");
+/// ```
+pub fn encode_to<W: Write + ?Sized>(input: impl AsRef<[u8]>, out: &mut W) -> fmt::Result {
+    EncodeOptions::new().encode_to(input, out)
+}
+
+/// 将十六进制文本解码为字节。
+///
+/// # 说明
+///
+/// 大小写均可，字节之间的 ASCII 空白字符会被忽略，因此可以直接解码 `de ad be ef` 这样按空格分隔的输入。遇到其他字符或末尾只剩一个数字时返回错误。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::hex::decode;
+/// assert_eq!(decode("DEADbeef").unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+/// assert_eq!(decode("e6 b5 8b\n").unwrap(), "测".as_bytes());
+/// assert!(decode("0x1f").is_err());
+/// ```
+///
+/// # 注意事项
+///
+/// - 解码以 `:` 等字符分隔的输入时，请使用 [`DecodeOptions::separator`]。
+pub fn decode(input: impl AsRef<[u8]>) -> Result<Vec<u8>, DecodeError> {
+    DecodeOptions::new().decode(input)
+}
+
+/// 将十六进制文本解码，并将结果追加到 `out`。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::hex::decode_to;
+/// let mut out = b"> ".to_vec();
+/// decode_to("6869", &mut out).unwrap();
+/// assert_eq!(out, b"> hi");
+/// ```
+pub fn decode_to(input: impl AsRef<[u8]>, out: &mut Vec<u8>) -> Result<(), DecodeError> {
+    DecodeOptions::new().decode_to(input, out)
+}
+
+/// 生成 `xxd` 格式的十六进制转储，用于调试二进制数据。
+///
+/// # 说明
+///
+/// 每行 16 字节，依次是 8 位十六进制的偏移、每 2 字节一组的十六进制数字和 ASCII 栏，ASCII 栏中不可打印的字节显示为 `.`。需要调整每行字节数、分组或起始偏移时，请使用 [`DumpOptions`]。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::hex::hexdump;
+/// assert_eq!(
+///     hexdump(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\x01"),
+///     concat!(
+///         "00000000: 8950 4e47 0d0a 1a0a 0000 000d 4948 4452  .PNG........IHDR\n",
+///         "00000010: 0000 01                                  ...\n",
+///     ),
+/// );
+/// ```
+pub fn hexdump(input: impl AsRef<[u8]>) -> String {
+    DumpOptions::new().dump(input)
+}
+
+/// 生成 `xxd` 格式的十六进制转储并写入 `out`。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::hex::hexdump_to;
+/// let mut log = String::from("payload:\n");
+/// hexdump_to("ok", &mut log).unwrap();
+/// assert_eq!(log, "payload:\n00000000: 6f6b                                     ok\n");
+/// ```
+pub fn hexdump_to<W: Write + ?Sized>(input: impl AsRef<[u8]>, out: &mut W) -> fmt::Result {
+    dump_into(input.as_ref(), &DumpOptions::new(), out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::Decoder;
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode(""), "");
+        assert_eq!(encode([0x00, 0x0f, 0xf0, 0xff]), "000ff0ff");
+        let upper = EncodeOptions::new().uppercase(true);
+        assert_eq!(upper.encode([0xab, 0xcd]), "ABCD");
+        assert_eq!(upper.separator(':').encode([0xab]), "AB");
+        assert_eq!(upper.separator(':').encode([0xab, 0xcd]), "AB:CD");
+        assert_eq!(
+            EncodeOptions::new().separator('·').encode([1, 2, 3]),
+            "01·02·03"
+        );
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let cases = [
+            ("0x1f", 1, DecodeErrorKind::InvalidByte),
+            ("abc", 2, DecodeErrorKind::InvalidLength),
+            ("a bc", 0, DecodeErrorKind::InvalidLength),
+            ("ab c\n", 3, DecodeErrorKind::InvalidLength),
+            ("ab\tc d", 3, DecodeErrorKind::InvalidLength),
+            ("ab:cd", 2, DecodeErrorKind::InvalidByte),
+            ("测", 0, DecodeErrorKind::InvalidByte),
+        ];
+        for (input, offset, kind) in cases {
+            let err = decode(input).unwrap_err();
+            assert_eq!(err.offset, offset, "{input}");
+            assert_eq!(err.kind, kind, "{input}");
+        }
+
+        let options = DecodeOptions::new().whitespace(false);
+        let err = options.decode("ab\ncd").unwrap_err();
+        assert_eq!((err.offset, err.kind), (2, DecodeErrorKind::InvalidByte));
+
+        // 要求输出文本时，偏移为解码结果中的字节偏移。
+        let err = DecodeOptions::new().decode_str("41 e6 b5").unwrap_err();
+        assert_eq!((err.offset, err.kind), (1, DecodeErrorKind::InvalidUtf8));
+        assert_eq!(DecodeOptions::new().decode_str("41e6b58b").unwrap(), "A测");
+    }
+
+    #[test]
+    fn test_separator() {
+        let colon = DecodeOptions::new().separator(':');
+        assert_eq!(colon.decode("00:1a:2B").unwrap(), [0x00, 0x1a, 0x2b]);
+        assert_eq!(colon.decode("001a::2b:").unwrap(), [0x00, 0x1a, 0x2b]);
+        assert_eq!(colon.decode("00: 1a").unwrap(), [0x00, 0x1a]);
+        let err = colon.decode("0:01a").unwrap_err();
+        assert_eq!((err.offset, err.kind), (0, DecodeErrorKind::InvalidLength));
+
+        let dot = DecodeOptions::new().separator('·').whitespace(false);
+        assert_eq!(dot.decode("01·02·03").unwrap(), [1, 2, 3]);
+        let err = dot.decode("01 02").unwrap_err();
+        assert_eq!((err.offset, err.kind), (2, DecodeErrorKind::InvalidByte));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let bytes: Vec<u8> = (0..=255).chain(0..1000).map(|i| i as u8).collect();
+        for uppercase in [false, true] {
+            for separator in [None, Some(':'), Some(' '), Some('·')] {
+                let mut encode = EncodeOptions::new().uppercase(uppercase);
+                let mut decode = DecodeOptions::new();
+                if let Some(c) = separator {
+                    encode = encode.separator(c);
+                    decode = decode.separator(c);
+                }
+                let encoded = encode.encode(&bytes);
+                assert_eq!(decode.decode(&encoded).unwrap(), bytes);
+                let mut out = String::new();
+                encode.encode_to(&bytes, &mut out).unwrap();
+                assert_eq!(out, encoded);
+            }
+        }
+    }
+}
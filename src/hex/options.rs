@@ -0,0 +1,150 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::Infallible;
+use core::fmt::{self, Write};
+
+use super::{decode_into, encode_into, DecodeError, DecodeErrorKind};
+use crate::codec::{decoded_str, Decoder, Encoder};
+
+/// 十六进制的编码选项。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::hex::EncodeOptions;
+/// let mac = EncodeOptions::new().uppercase(true).separator(':');
+/// assert_eq!(mac.encode([0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e]), "00:1A:2B:3C:4D:5E");
+/// assert_eq!(EncodeOptions::new().separator(' ').encode("hi"), "68 69");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EncodeOptions {
+    pub(crate) uppercase: bool,
+    pub(crate) separator: Option<char>,
+}
+
+impl EncodeOptions {
+    /// 创建默认的编码选项：小写，不加分隔符。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置是否使用大写字母 `A-F`，默认使用小写。
+    pub fn uppercase(mut self, uppercase: bool) -> Self {
+        self.uppercase = uppercase;
+        self
+    }
+
+    /// 设置每个字节之间的分隔符，如 `:` 或空格，默认不加。
+    pub fn separator(mut self, separator: char) -> Self {
+        self.separator = Some(separator);
+        self
+    }
+
+    /// 按当前选项编码。
+    pub fn encode(&self, input: impl AsRef<[u8]>) -> String {
+        let input = input.as_ref();
+        let sep = self.separator.map_or(0, char::len_utf8);
+        let mut out = String::with_capacity(input.len() * (2 + sep));
+        let _ = encode_into(input, self, &mut out);
+        out
+    }
+
+    /// 按当前选项编码并写入 `out`，不分配中间字符串。
+    pub fn encode_to<W: Write + ?Sized>(
+        &self,
+        input: impl AsRef<[u8]>,
+        out: &mut W,
+    ) -> fmt::Result {
+        encode_into(input.as_ref(), self, out)
+    }
+}
+
+/// 十六进制的解码选项。
+///
+/// # 说明
+///
+/// 默认选项与 [`decode`](super::decode) 的行为一致：大小写均可，忽略字节之间的 ASCII 空白字符。设置分隔符后，字节之间的分隔符同样被忽略，因此分隔符可有可无，但不能出现在一个字节的两个数字之间。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::hex::DecodeOptions;
+/// let options = DecodeOptions::new().separator(':');
+/// assert_eq!(options.decode("00:1A:2b").unwrap(), [0x00, 0x1a, 0x2b]);
+/// assert!(options.decode("0:01A").is_err());
+/// assert!(DecodeOptions::new().whitespace(false).decode("de ad").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeOptions {
+    pub(crate) separator: Option<char>,
+    pub(crate) whitespace: bool,
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        Self {
+            separator: None,
+            whitespace: true,
+        }
+    }
+}
+
+impl DecodeOptions {
+    /// 创建默认的解码选项。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置字节之间可以出现的分隔符，默认没有。
+    pub fn separator(mut self, separator: char) -> Self {
+        self.separator = Some(separator);
+        self
+    }
+
+    /// 设置是否忽略字节之间的 ASCII 空白字符，默认忽略；不忽略时空白字符视为无效字符。
+    pub fn whitespace(mut self, whitespace: bool) -> Self {
+        self.whitespace = whitespace;
+        self
+    }
+
+    /// 按当前选项解码。
+    pub fn decode(&self, input: impl AsRef<[u8]>) -> Result<Vec<u8>, DecodeError> {
+        let input = input.as_ref();
+        let mut out = Vec::with_capacity(input.len() / 2);
+        decode_into(input, self, &mut out)?;
+        Ok(out)
+    }
+
+    /// 按当前选项解码，并将结果追加到 `out`；出错时 `out` 中可能已追加部分结果。
+    pub fn decode_to(&self, input: impl AsRef<[u8]>, out: &mut Vec<u8>) -> Result<(), DecodeError> {
+        decode_into(input.as_ref(), self, out)
+    }
+}
+
+impl Decoder for DecodeOptions {
+    type Error = DecodeError;
+
+    fn decode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, DecodeError> {
+        decoded_str(self.decode(input)?, |offset| DecodeError {
+            offset,
+            kind: DecodeErrorKind::InvalidUtf8,
+        })
+    }
+
+    fn decode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, DecodeError> {
+        self.decode(input).map(Cow::Owned)
+    }
+}
+
+impl Encoder for EncodeOptions {
+    type Error = Infallible;
+
+    fn encode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, Infallible> {
+        Ok(Cow::Owned(self.encode(input)))
+    }
+
+    fn encode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, Infallible> {
+        Ok(Cow::Owned(self.encode(input).into_bytes()))
+    }
+}
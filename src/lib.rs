@@ -1,9 +1,42 @@
+//! 这是一个用于各种实用功能的 Rust 库。
+//!
+//! 各编解码模块都实现了 [`Encoder`] 和 [`Decoder`]，可以在泛型代码中统一使用、通过 `then` 链式组合，或用 [`encoder`]、[`decoder`] 按名称在运行时选择。
+//!
+//! # 特性
+//!
+//! - `std`（默认启用）：依赖标准库，提供基于 `std::io` 的流式编解码器；启用时会同时启用 `alloc`。
+//! - `alloc`：只依赖 `alloc`，在 `#![no_std]` 环境中提供 [`ascii`]、[`unicode`]、[`percent`]、[`base64`]、[`base32`]、[`hex`] 等模块的编解码功能。
+//! - `serde`：提供 `ascii::serde` 适配器，通过 `#[serde(with = "utils_rust::ascii::serde")]` 在序列化时编码、反序列化时解码字符串字段。
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 // region: ---- 模块
 
 // -- 展开模块
 
+#[cfg(feature = "alloc")]
+mod codec;
+#[cfg(feature = "alloc")]
+pub use codec::{
+    decoder, encoder, BoxError, Chain, ChainError, Decoder, DynDecoder, DynEncoder, Encoder,
+    ErrorPolicy, CODECS,
+};
+
 // -- 公开模块
 
+#[cfg(feature = "alloc")]
 pub mod ascii;
+#[cfg(feature = "alloc")]
+pub mod base32;
+#[cfg(feature = "alloc")]
+pub mod base64;
+#[cfg(feature = "alloc")]
+pub mod hex;
+#[cfg(feature = "alloc")]
+pub mod percent;
+#[cfg(feature = "alloc")]
+pub mod unicode;
 
 // endregion: ---- 模块
@@ -0,0 +1,54 @@
+use alloc::string::String;
+use core::error::Error;
+use core::fmt;
+
+/// 百分号编码解析失败的原因。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeErrorKind {
+    /// `%` 之后没有两位十六进制数字，如 `%zz`、`100%`。
+    InvalidEscape,
+    /// 解码得到的字节不是有效的 UTF-8，如单独的 `%FF`。
+    InvalidUtf8,
+}
+
+impl fmt::Display for DecodeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DecodeErrorKind::InvalidEscape => "`%` 之后缺少两位十六进制数字",
+            DecodeErrorKind::InvalidUtf8 => "解码结果不是有效的 UTF-8",
+        })
+    }
+}
+
+/// 解码百分号编码时遇到的错误。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::percent::{try_decode, DecodeErrorKind};
+/// let err = try_decode("a%E6%B5b").unwrap_err();
+/// assert_eq!(err.offset, 1);
+/// assert_eq!(err.escape, "%E6%B5");
+/// assert_eq!(err.kind, DecodeErrorKind::InvalidUtf8);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    /// 出错的编码序列在输入中的字节偏移（指向 `%`）。
+    pub offset: usize,
+    /// 出错的编码序列原文；格式不正确时只包含 `%` 及其后的有效十六进制数字。
+    pub escape: String,
+    /// 出错原因。
+    pub kind: DecodeErrorKind,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "第 {} 字节处的编码序列 `{}` 无效：{}",
+            self.offset, self.escape, self.kind
+        )
+    }
+}
+
+impl Error for DecodeError {}
@@ -0,0 +1,559 @@
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+use core::str;
+
+mod error;
+mod options;
+
+pub use crate::codec::ErrorPolicy;
+pub use error::{DecodeError, DecodeErrorKind};
+pub use options::{DecodeOptions, EncodeOptions, EncodeSet};
+
+/// 解码核心实现可能遇到的错误。
+#[derive(Debug)]
+enum Error {
+    Decode(DecodeError),
+    Fmt(fmt::Error),
+}
+
+impl From<fmt::Error> for Error {
+    fn from(e: fmt::Error) -> Self {
+        Error::Fmt(e)
+    }
+}
+
+/// 查找第一个需要解码的 `%`（表单格式下还包括 `+`）的位置。
+fn find_escape(bytes: &[u8], form: bool) -> Option<usize> {
+    bytes.iter().position(|&b| b == b'%' || (form && b == b'+'))
+}
+
+/// 解析以 `%` 开头的 `%XX`，格式不正确时返回 `None`。
+fn parse_escape(input: &[u8]) -> Option<u8> {
+    let hex = |b: u8| char::from(b).to_digit(16);
+    match input {
+        [b'%', high, low, ..] => Some((hex(*high)? << 4 | hex(*low)?) as u8),
+        _ => None,
+    }
+}
+
+/// 生成格式不正确的 `%` 对应的错误，`escape` 只包含 `%` 及其后的有效十六进制数字。
+fn invalid_escape(bytes: &[u8], offset: usize) -> DecodeError {
+    let digits = bytes[offset + 1..]
+        .iter()
+        .take(2)
+        .take_while(|b| b.is_ascii_hexdigit())
+        .count();
+    DecodeError {
+        offset,
+        escape: bytes[offset..=offset + digits].escape_ascii().to_string(),
+        kind: DecodeErrorKind::InvalidEscape,
+    }
+}
+
+/// 按选项解码并返回新分配的字符串。
+fn decode_with(s: &str, options: &DecodeOptions) -> Result<String, DecodeError> {
+    let mut out = String::with_capacity(s.len());
+    decode_string(s, options, &mut out)?;
+    Ok(out)
+}
+
+/// 按选项解码；没有需要解码的内容时直接借用输入。
+fn decode_cow_with<'s>(s: &'s str, options: &DecodeOptions) -> Result<Cow<'s, str>, DecodeError> {
+    if find_escape(s.as_bytes(), options.form).is_some() {
+        decode_with(s, options).map(Cow::Owned)
+    } else {
+        Ok(Cow::Borrowed(s))
+    }
+}
+
+/// 按选项解码并将结果追加到 `out`，写入 `String` 不会出现格式化错误。
+fn decode_string(s: &str, options: &DecodeOptions, out: &mut String) -> Result<(), DecodeError> {
+    decode_into(s, options, out).map_err(|e| match e {
+        Error::Decode(e) => e,
+        Error::Fmt(_) => unreachable!("写入 String 不会失败"),
+    })
+}
+
+/// 解码的核心实现，将结果写入 `out`；连续的 `%XX` 先还原为字节，再按 UTF-8 解码。
+fn decode_into<W: Write + ?Sized>(
+    s: &str,
+    options: &DecodeOptions,
+    out: &mut W,
+) -> Result<(), Error> {
+    let bytes = s.as_bytes();
+    let mut buf = Vec::new();
+    let mut start = 0;
+    while let Some(i) = find_escape(&bytes[start..], options.form) {
+        let i = start + i;
+        out.write_str(&s[start..i])?;
+        if bytes[i] == b'+' {
+            out.write_char(' ')?;
+            start = i + 1;
+            continue;
+        }
+
+        buf.clear();
+        let mut end = i;
+        while let Some(b) = parse_escape(&bytes[end..]) {
+            buf.push(b);
+            end += 3;
+        }
+        if buf.is_empty() {
+            if options.policy == ErrorPolicy::Strict {
+                return Err(Error::Decode(invalid_escape(bytes, i)));
+            }
+            out.write_char('%')?;
+            start = i + 1;
+            continue;
+        }
+        write_utf8(&s[i..end], &buf, i, options.policy, out)?;
+        start = end;
+    }
+    out.write_str(&s[start..])?;
+    Ok(())
+}
+
+/// 将一段连续的 `%XX`（原文为 `escape`，起始偏移为 `offset`）解码得到的字节按 UTF-8 写入 `out`，非法的序列按策略处理。
+fn write_utf8<W: Write + ?Sized>(
+    escape: &str,
+    bytes: &[u8],
+    offset: usize,
+    policy: ErrorPolicy,
+    out: &mut W,
+) -> Result<(), Error> {
+    // 每个字节在原文中占 3 个字符。
+    let mut pos = 0;
+    for chunk in bytes.utf8_chunks() {
+        out.write_str(chunk.valid())?;
+        pos += 3 * chunk.valid().len();
+        let len = 3 * chunk.invalid().len();
+        if len == 0 {
+            continue;
+        }
+        let invalid = &escape[pos..pos + len];
+        match policy {
+            ErrorPolicy::Strict => {
+                return Err(Error::Decode(DecodeError {
+                    offset: offset + pos,
+                    escape: invalid.to_string(),
+                    kind: DecodeErrorKind::InvalidUtf8,
+                }));
+            }
+            ErrorPolicy::Replace => out.write_char(char::REPLACEMENT_CHARACTER)?,
+            ErrorPolicy::Passthrough => out.write_str(invalid)?,
+            ErrorPolicy::Drop => {}
+        }
+        pos += len;
+    }
+    Ok(())
+}
+
+/// 按选项解码字节，结果不要求是 UTF-8；没有需要解码的内容时直接借用输入。
+fn decode_bytes_with<'b>(
+    bytes: &'b [u8],
+    options: &DecodeOptions,
+) -> Result<Cow<'b, [u8]>, DecodeError> {
+    if find_escape(bytes, options.form).is_none() {
+        return Ok(Cow::Borrowed(bytes));
+    }
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut start = 0;
+    while let Some(i) = find_escape(&bytes[start..], options.form) {
+        let i = start + i;
+        out.extend_from_slice(&bytes[start..i]);
+        start = i + 1;
+        match (bytes[i], parse_escape(&bytes[i..])) {
+            (b'+', _) => out.push(b' '),
+            (_, Some(b)) => {
+                out.push(b);
+                start = i + 3;
+            }
+            (_, None) if options.policy == ErrorPolicy::Strict => {
+                return Err(invalid_escape(bytes, i));
+            }
+            (_, None) => out.push(b'%'),
+        }
+    }
+    out.extend_from_slice(&bytes[start..]);
+    Ok(Cow::Owned(out))
+}
+
+/// 将 URL 中的百分号编码（如 `%E6%B5%8B`）还原为原始字符。
+///
+/// # 说明
+///
+/// 连续的 `%XX` 先还原为字节，再按 UTF-8 解码，解码结果中非法的 UTF-8 序列转换为 U+FFFD（`�`）。`+` 按原样保留；解码 HTML 表单提交的数据或查询参数时，请使用 [`DecodeOptions::form`] 将 `+` 解码为空格。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::percent::decode;
+/// assert_eq!(decode("%E6%B5%8B%E8%AF%95"), "测试");
+/// assert_eq!(decode("a%20b+c%2Fd"), "a b+c/d");
+/// assert_eq!(decode("%FF"), "\u{FFFD}");
+/// assert_eq!(decode("100%"), "100%");
+/// ```
+///
+/// # 注意事项
+///
+/// - 格式不正确的 `%`（如 `%zz`、`100%`）会原样保留，与浏览器的处理方式一致。
+/// - 需要其他处理方式时，请使用 [`DecodeOptions`]；需要得知解析失败的位置和原因时，请使用 [`try_decode`]。
+/// - 解码结果不是文本时，请使用 [`decode_bytes`]。
+pub fn decode(s: &str) -> String {
+    decode_with(s, &DecodeOptions::new()).unwrap_or_default()
+}
+
+/// 严格解码百分号编码，遇到格式不正确的 `%` 或解码结果不是有效的 UTF-8 时返回错误。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::percent::{try_decode, DecodeErrorKind};
+/// assert_eq!(try_decode("%E6%B5%8B").unwrap(), "测");
+/// assert_eq!(try_decode("100%").unwrap_err().kind, DecodeErrorKind::InvalidEscape);
+/// assert_eq!(try_decode("%FF").unwrap_err().kind, DecodeErrorKind::InvalidUtf8);
+/// ```
+pub fn try_decode(s: &str) -> Result<String, DecodeError> {
+    decode_with(s, &DecodeOptions::new().policy(ErrorPolicy::Strict))
+}
+
+/// 解码百分号编码并将结果写入 `out`，不分配结果字符串。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::percent::decode_to;
+/// let mut out = String::from("> ");
+/// decode_to("%E6%B5%8B", &mut out).unwrap();
+/// assert_eq!(out, "> 测");
+/// ```
+pub fn decode_to<W: Write + ?Sized>(s: &str, out: &mut W) -> fmt::Result {
+    decode_into(s, &DecodeOptions::new(), out).map_err(|_| fmt::Error)
+}
+
+/// 解码百分号编码，输入中没有 `%` 时直接借用输入。
+///
+/// # 示例
+///
+/// ```rust
+/// # use std::borrow::Cow;
+/// # use utils_rust::percent::decode_cow;
+/// assert!(matches!(decode_cow("plain"), Cow::Borrowed("plain")));
+/// assert_eq!(decode_cow("%E6%B5%8B"), "测");
+/// ```
+pub fn decode_cow(s: &str) -> Cow<'_, str> {
+    decode_cow_with(s, &DecodeOptions::new()).unwrap_or_default()
+}
+
+/// 将百分号编码还原为字节，结果不要求是 UTF-8。
+///
+/// # 说明
+///
+/// 解码规则与 [`decode`] 相同，但不对结果做 UTF-8 解码，适用于编码了二进制数据或 GBK 等其他字符集的 URL。输入中没有 `%` 时直接借用输入。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::percent::decode_bytes;
+/// assert_eq!(decode_bytes(b"%B2%E2%CA%D4"), &b"\xb2\xe2\xca\xd4"[..]);
+/// assert_eq!(decode_bytes(b"%zz"), &b"%zz"[..]);
+/// ```
+pub fn decode_bytes(bytes: &[u8]) -> Cow<'_, [u8]> {
+    decode_bytes_with(bytes, &DecodeOptions::new()).unwrap_or_default()
+}
+
+/// 按选项编码并返回新分配的字符串。
+fn encode_with(s: &str, options: &EncodeOptions) -> String {
+    let mut out = String::with_capacity(s.len());
+    let _ = encode_into(s.as_bytes(), options, &mut out);
+    out
+}
+
+/// 按选项编码；没有需要转义的字符时直接借用输入。
+fn encode_cow_with<'s>(s: &'s str, options: &EncodeOptions) -> Cow<'s, str> {
+    if options.set.find(s.as_bytes()).is_some() {
+        Cow::Owned(encode_with(s, options))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+/// 按选项编码字节；没有需要转义的字节时直接借用输入。
+fn encode_bytes_with<'b>(bytes: &'b [u8], options: &EncodeOptions) -> Cow<'b, [u8]> {
+    if options.set.find(bytes).is_none() {
+        return Cow::Borrowed(bytes);
+    }
+    let mut out = String::with_capacity(bytes.len());
+    let _ = encode_into(bytes, options, &mut out);
+    Cow::Owned(out.into_bytes())
+}
+
+/// 编码的核心实现，将结果写入 `out`。
+fn encode_into<W: Write + ?Sized>(
+    bytes: &[u8],
+    options: &EncodeOptions,
+    out: &mut W,
+) -> fmt::Result {
+    // 不需要转义的字节都是 ASCII 字符，可以直接作为文本写入。
+    let ascii = |bytes| str::from_utf8(bytes).unwrap_or_default();
+    let set = options.set;
+    let mut start = 0;
+    while let Some(i) = set.find(&bytes[start..]) {
+        let i = start + i;
+        out.write_str(ascii(&bytes[start..i]))?;
+        match bytes[i] {
+            b' ' if set == EncodeSet::Form => out.write_char('+')?,
+            b => write!(out, "%{b:02X}")?,
+        }
+        start = i + 1;
+    }
+    out.write_str(ascii(&bytes[start..]))
+}
+
+/// 将字符串编码为 URL 中使用的百分号编码，结果可以放入 URL 的任意位置。
+///
+/// # 说明
+///
+/// 除 RFC 3986 的非保留字符 `A-Z a-z 0-9 - . _ ~` 外，其余字符均按 UTF-8 字节写为大写十六进制的 `%XX`。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::percent::encode;
+/// assert_eq!(encode("测试"), "%E6%B5%8B%E8%AF%95");
+/// assert_eq!(encode("a b&c=d/e"), "a%20b%26c%3Dd%2Fe");
+/// assert_eq!(encode("-._~"), "-._~");
+/// ```
+///
+/// # 注意事项
+///
+/// - 编码完整的路径、查询字符串或表单数据时，请使用 [`EncodeOptions`] 选择对应的 [`EncodeSet`]。
+/// - 需要编码非 UTF-8 的数据时，请使用 [`encode_bytes`]。
+pub fn encode(s: &str) -> String {
+    encode_with(s, &EncodeOptions::new())
+}
+
+/// 编码字符串并将结果写入 `out`，不分配中间字符串。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::percent::encode_to;
+/// let mut url = String::from("/search?q=");
+/// encode_to("测 试", &mut url).unwrap();
+/// assert_eq!(url, "/search?q=%E6%B5%8B%20%E8%AF%95");
+/// ```
+pub fn encode_to<W: Write + ?Sized>(s: &str, out: &mut W) -> fmt::Result {
+    encode_into(s.as_bytes(), &EncodeOptions::new(), out)
+}
+
+/// 编码字符串，没有需要转义的字符时直接借用输入。
+///
+/// # 示例
+///
+/// ```rust
+/// # use std::borrow::Cow;
+/// # use utils_rust::percent::encode_cow;
+/// assert!(matches!(encode_cow("plain"), Cow::Borrowed("plain")));
+/// assert_eq!(encode_cow("测"), "%E6%B5%8B");
+/// ```
+pub fn encode_cow(s: &str) -> Cow<'_, str> {
+    encode_cow_with(s, &EncodeOptions::new())
+}
+
+/// 将任意字节编码为百分号编码，输入不要求是 UTF-8。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::percent::encode_bytes;
+/// assert_eq!(encode_bytes(b"\xb2\xe2 a"), &b"%B2%E2%20a"[..]);
+/// ```
+pub fn encode_bytes(bytes: &[u8]) -> Cow<'_, [u8]> {
+    encode_bytes_with(bytes, &EncodeOptions::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("%E6%B5%8B%E8%AF%95"), "测试");
+        assert_eq!(decode("%e6%b5%8b"), "测");
+        assert_eq!(decode("a%20b%2Bc+d"), "a b+c+d");
+        assert_eq!(decode("%F0%9F%91%8D 测"), "👍 测");
+        assert_eq!(decode("no escapes"), "no escapes");
+    }
+
+    #[test]
+    fn test_decode_utf8() {
+        assert_eq!(decode("%FF%41"), "\u{FFFD}A");
+        assert_eq!(decode("%E6%B5"), "\u{FFFD}");
+        assert_eq!(decode("%E6%B5x%8B"), "\u{FFFD}x\u{FFFD}");
+        assert_eq!(decode("%C0%AF"), "\u{FFFD}\u{FFFD}");
+        assert_eq!(decode("%ED%A0%80"), "\u{FFFD}\u{FFFD}\u{FFFD}");
+        // 编码序列中间插入格式不正确的 `%` 时分段解码。
+        assert_eq!(decode("%E6%%B5%8B"), "\u{FFFD}%\u{FFFD}\u{FFFD}");
+    }
+
+    #[test]
+    fn test_decode_form() {
+        let options = DecodeOptions::new().form(true);
+        assert_eq!(options.decode("a+b%2Bc").unwrap(), "a b+c");
+        assert_eq!(
+            options.decode("q=%E6%B5%8B+%E8%AF%95&x=1").unwrap(),
+            "q=测 试&x=1"
+        );
+        assert_eq!(options.decode_cow("a+b").unwrap(), "a b");
+        assert!(matches!(options.decode_cow("ab"), Ok(Cow::Borrowed(_))));
+        assert!(matches!(decode_cow("a+b"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_decode_malformed() {
+        assert_eq!(decode("100% %zz %4 %4g %"), "100% %zz %4 %4g %");
+        assert_eq!(decode("%%41"), "%A");
+        assert_eq!(decode("%测"), "%测");
+    }
+
+    #[test]
+    fn test_decode_policy() {
+        let input = "a%FFb%zz%E6%B5%8B%C0";
+        let decode = |policy| DecodeOptions::new().policy(policy).decode(input);
+        assert_eq!(decode(ErrorPolicy::Drop).unwrap(), "ab%zz测");
+        assert_eq!(
+            decode(ErrorPolicy::Replace).unwrap(),
+            "a\u{FFFD}b%zz测\u{FFFD}"
+        );
+        assert_eq!(decode(ErrorPolicy::Passthrough).unwrap(), "a%FFb%zz测%C0");
+        assert!(decode(ErrorPolicy::Strict).is_err());
+    }
+
+    #[test]
+    fn test_try_decode() {
+        let cases = [
+            ("ab%", 2, "%", DecodeErrorKind::InvalidEscape),
+            ("%zz", 0, "%", DecodeErrorKind::InvalidEscape),
+            ("测%4g", 3, "%4", DecodeErrorKind::InvalidEscape),
+            ("%4", 0, "%4", DecodeErrorKind::InvalidEscape),
+            ("%FF", 0, "%FF", DecodeErrorKind::InvalidUtf8),
+            ("%41%E6%B5", 3, "%E6%B5", DecodeErrorKind::InvalidUtf8),
+            ("%E6%B5%8B%80%41", 9, "%80", DecodeErrorKind::InvalidUtf8),
+        ];
+        for (input, offset, escape, kind) in cases {
+            let err = try_decode(input).unwrap_err();
+            assert_eq!(err.offset, offset, "{input}");
+            assert_eq!(err.escape, escape, "{input}");
+            assert_eq!(err.kind, kind, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_decode_bytes() {
+        assert_eq!(decode_bytes(b"%FF%00a"), &b"\xff\0a"[..]);
+        assert_eq!(decode_bytes(b"a+b\xff%"), &b"a+b\xff%"[..]);
+        assert!(matches!(decode_bytes(b"plain\xff"), Cow::Borrowed(_)));
+
+        let options = DecodeOptions::new().form(true).policy(ErrorPolicy::Strict);
+        assert_eq!(options.decode_bytes(b"a+%FF").unwrap(), &b"a \xff"[..]);
+        let err = options.decode_bytes(b"\xff%4g").unwrap_err();
+        assert_eq!((err.offset, err.escape.as_str()), (1, "%4"));
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode("测试"), "%E6%B5%8B%E8%AF%95");
+        assert_eq!(encode("👍"), "%F0%9F%91%8D");
+        assert_eq!(
+            encode("a b&c=d/e?f#g~h-i_j.k%"),
+            "a%20b%26c%3Dd%2Fe%3Ff%23g~h-i_j.k%25"
+        );
+        assert_eq!(encode("\0\n\u{7F}"), "%00%0A%7F");
+        assert_eq!(encode("plain"), "plain");
+    }
+
+    #[test]
+    fn test_encode_sets() {
+        let ascii: String = (0x20u8..0x7F).map(char::from).collect();
+        let kept = |set| -> String {
+            ascii
+                .chars()
+                .filter(|&c| !EncodeSet::encodes(set, c))
+                .collect()
+        };
+        let alnum = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+        let sorted = |extra: &str| -> String {
+            let mut chars: Vec<char> = alnum.chars().chain(extra.chars()).collect();
+            chars.sort_unstable();
+            chars.into_iter().collect()
+        };
+        assert_eq!(kept(EncodeSet::Component), sorted("-._~"));
+        assert_eq!(kept(EncodeSet::Path), sorted("-._~!$&'()*+,;=:@/"));
+        assert_eq!(kept(EncodeSet::Query), sorted("-._~!$&'()*+,;=:@/?"));
+        assert_eq!(kept(EncodeSet::Fragment), kept(EncodeSet::Query));
+        assert_eq!(kept(EncodeSet::Userinfo), sorted("-._~!$&'()*+,;=:"));
+        assert_eq!(kept(EncodeSet::Form), sorted("*-._"));
+        for set in [
+            EncodeSet::Component,
+            EncodeSet::Path,
+            EncodeSet::Query,
+            EncodeSet::Fragment,
+            EncodeSet::Userinfo,
+            EncodeSet::Form,
+        ] {
+            assert!(EncodeSet::encodes(set, '%'));
+            assert!(EncodeSet::encodes(set, '测'));
+        }
+    }
+
+    #[test]
+    fn test_encode_form() {
+        let options = EncodeOptions::new().set(EncodeSet::Form);
+        assert_eq!(options.encode("a b+c&d=测~"), "a+b%2Bc%26d%3D%E6%B5%8B%7E");
+        let decoded = DecodeOptions::new().form(true).decode("a+b%2Bc");
+        assert_eq!(decoded.unwrap(), "a b+c");
+    }
+
+    #[test]
+    fn test_encode_bytes() {
+        assert_eq!(encode_bytes(b"\x00\xff a"), &b"%00%FF%20a"[..]);
+        assert!(matches!(encode_bytes(b"plain"), Cow::Borrowed(_)));
+        let options = EncodeOptions::new().set(EncodeSet::Form);
+        assert_eq!(options.encode_bytes(b"a b\xb2"), &b"a+b%B2"[..]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let text = "测试 👍 a+b&c=d/e?f#g %25 \0\n\u{7F}\u{80}\u{FFFF}\u{10FFFF}";
+        for set in [
+            EncodeSet::Component,
+            EncodeSet::Path,
+            EncodeSet::Query,
+            EncodeSet::Fragment,
+            EncodeSet::Userinfo,
+        ] {
+            let encoded = EncodeOptions::new().set(set).encode(text);
+            assert!(encoded.is_ascii());
+            assert_eq!(try_decode(&encoded).unwrap(), text);
+        }
+        let encoded = EncodeOptions::new().set(EncodeSet::Form).encode(text);
+        let options = DecodeOptions::new().form(true).policy(ErrorPolicy::Strict);
+        assert_eq!(options.decode(&encoded).unwrap(), text);
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(decode_bytes(&encode_bytes(&bytes)), &bytes[..]);
+    }
+
+    #[test]
+    fn test_cow() {
+        assert!(matches!(decode_cow("plain"), Cow::Borrowed(_)));
+        assert!(matches!(decode_cow("%41"), Cow::Owned(_)));
+        assert!(matches!(encode_cow("plain"), Cow::Borrowed(_)));
+        assert!(matches!(encode_cow("a b"), Cow::Owned(_)));
+        let options = EncodeOptions::new().set(EncodeSet::Path);
+        assert!(matches!(options.encode_cow("/a/b"), Cow::Borrowed(_)));
+    }
+}
@@ -0,0 +1,200 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::convert::Infallible;
+use core::fmt::{self, Write};
+
+use super::{
+    decode_bytes_with, decode_cow_with, decode_with, encode_bytes_with, encode_cow_with,
+    encode_into, encode_with, DecodeError, ErrorPolicy,
+};
+use crate::codec::{Decoder, Encoder};
+
+/// 百分号编码的解码选项。
+///
+/// # 说明
+///
+/// 默认选项与 [`decode`](super::decode) 的行为一致：`+` 按原样保留，解码结果中非法的 UTF-8 序列被替换为 U+FFFD，格式不正确的 `%` 按原文保留。解码 HTML 表单或查询参数时，请开启 [`DecodeOptions::form`]。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::percent::{DecodeOptions, ErrorPolicy};
+/// let input = "a+%E6%B5%8B%FF";
+/// let decode = |policy| DecodeOptions::new().form(true).policy(policy).decode(input);
+/// assert_eq!(decode(ErrorPolicy::Drop).unwrap(), "a 测");
+/// assert_eq!(decode(ErrorPolicy::Replace).unwrap(), "a 测\u{FFFD}");
+/// assert_eq!(decode(ErrorPolicy::Passthrough).unwrap(), "a 测%FF");
+/// assert!(decode(ErrorPolicy::Strict).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecodeOptions {
+    pub(crate) policy: ErrorPolicy,
+    pub(crate) form: bool,
+}
+
+impl DecodeOptions {
+    /// 创建默认的解码选项。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置解码结果中非法 UTF-8 序列的处理方式，默认为 [`ErrorPolicy::Replace`]：
+    ///
+    /// - [`ErrorPolicy::Strict`]：返回 [`DecodeError`]；之后没有两位十六进制数字的 `%`（如 `%zz`、`100%`）同样视为错误。
+    /// - [`ErrorPolicy::Replace`]：每个非法的 UTF-8 序列（如单独的 `%FF`）替换为 U+FFFD（`�`）。
+    /// - [`ErrorPolicy::Passthrough`]：保留非法序列对应的 `%XX` 原文。
+    /// - [`ErrorPolicy::Drop`]：删除非法序列对应的 `%XX`。
+    ///
+    /// 非严格模式下，格式不正确的 `%` 总是按原文保留；解码为字节时不检查 UTF-8，只有 [`ErrorPolicy::Strict`] 会影响结果。
+    pub fn policy(mut self, policy: ErrorPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// 设置是否按 `application/x-www-form-urlencoded` 格式解码，即将 `+` 解码为空格，默认关闭。
+    pub fn form(mut self, form: bool) -> Self {
+        self.form = form;
+        self
+    }
+
+    /// 按当前选项解码字符串，仅在 [`ErrorPolicy::Strict`] 下可能返回错误。
+    pub fn decode(&self, s: &str) -> Result<String, DecodeError> {
+        decode_with(s, self)
+    }
+
+    /// 按当前选项解码字符串，没有需要解码的内容时直接借用输入。
+    pub fn decode_cow<'s>(&self, s: &'s str) -> Result<Cow<'s, str>, DecodeError> {
+        decode_cow_with(s, self)
+    }
+
+    /// 按当前选项解码字节，结果不要求是 UTF-8；仅在 [`ErrorPolicy::Strict`] 下因格式不正确的 `%` 返回错误。
+    pub fn decode_bytes<'b>(&self, bytes: &'b [u8]) -> Result<Cow<'b, [u8]>, DecodeError> {
+        decode_bytes_with(bytes, self)
+    }
+}
+
+/// 编码时不需要转义的字符集合，按 RFC 3986 中 URL 各组成部分允许出现的字符划分。
+///
+/// # 说明
+///
+/// 各集合都只保留 ASCII 字符，非 ASCII 字符总是按 UTF-8 字节转义，`%` 总是转义为 `%25`。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EncodeSet {
+    /// 只保留非保留字符 `A-Z a-z 0-9 - . _ ~`，适用于路径片段、参数名或参数值等任意位置。
+    #[default]
+    Component,
+    /// 路径，额外保留 `/`、`:`、`@` 和子分隔符 `! $ & ' ( ) * + , ; =`。
+    Path,
+    /// 查询字符串，在 [`EncodeSet::Path`] 的基础上额外保留 `?`。
+    Query,
+    /// 片段标识符，保留的字符与 [`EncodeSet::Query`] 相同。
+    Fragment,
+    /// 用户信息，保留非保留字符、子分隔符和 `:`。
+    Userinfo,
+    /// HTML 表单的 `application/x-www-form-urlencoded` 格式，只保留字母数字和 `* - . _`，空格写为 `+`。
+    Form,
+}
+
+impl EncodeSet {
+    /// 判断 ASCII 字节在当前集合中是否可以原样保留。
+    fn allows(self, b: u8) -> bool {
+        let unreserved = b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~');
+        let sub_delim = matches!(
+            b,
+            b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b',' | b';' | b'='
+        );
+        let pchar = unreserved || sub_delim || matches!(b, b':' | b'@');
+        match self {
+            EncodeSet::Component => unreserved,
+            EncodeSet::Path => pchar || b == b'/',
+            EncodeSet::Query | EncodeSet::Fragment => pchar || matches!(b, b'/' | b'?'),
+            EncodeSet::Userinfo => unreserved || sub_delim || b == b':',
+            EncodeSet::Form => b.is_ascii_alphanumeric() || matches!(b, b'*' | b'-' | b'.' | b'_'),
+        }
+    }
+
+    /// 判断字符在当前集合中是否需要转义。
+    pub(crate) fn encodes(self, c: char) -> bool {
+        !c.is_ascii() || !self.allows(c as u8)
+    }
+
+    /// 查找第一个需要转义的字节的位置。
+    pub(crate) fn find(self, bytes: &[u8]) -> Option<usize> {
+        bytes.iter().position(|&b| !b.is_ascii() || !self.allows(b))
+    }
+}
+
+/// 百分号编码的编码选项。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::percent::{EncodeOptions, EncodeSet};
+/// let encode = |set| EncodeOptions::new().set(set).encode("/a b?c=测");
+/// assert_eq!(encode(EncodeSet::Component), "%2Fa%20b%3Fc%3D%E6%B5%8B");
+/// assert_eq!(encode(EncodeSet::Path), "/a%20b%3Fc=%E6%B5%8B");
+/// assert_eq!(encode(EncodeSet::Query), "/a%20b?c=%E6%B5%8B");
+/// assert_eq!(encode(EncodeSet::Form), "%2Fa+b%3Fc%3D%E6%B5%8B");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EncodeOptions {
+    pub(crate) set: EncodeSet,
+}
+
+impl EncodeOptions {
+    /// 创建默认的编码选项。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置不需要转义的字符集合。
+    pub fn set(mut self, set: EncodeSet) -> Self {
+        self.set = set;
+        self
+    }
+
+    /// 按当前选项编码字符串。
+    pub fn encode(&self, s: &str) -> String {
+        encode_with(s, self)
+    }
+
+    /// 按当前选项编码字符串并写入 `out`，不分配中间字符串。
+    pub fn encode_to<W: Write + ?Sized>(&self, s: &str, out: &mut W) -> fmt::Result {
+        encode_into(s.as_bytes(), self, out)
+    }
+
+    /// 按当前选项编码字符串，没有需要转义的字符时直接借用输入。
+    pub fn encode_cow<'s>(&self, s: &'s str) -> Cow<'s, str> {
+        encode_cow_with(s, self)
+    }
+
+    /// 按当前选项编码任意字节，输入不要求是 UTF-8。
+    pub fn encode_bytes<'b>(&self, bytes: &'b [u8]) -> Cow<'b, [u8]> {
+        encode_bytes_with(bytes, self)
+    }
+}
+
+impl Decoder for DecodeOptions {
+    type Error = DecodeError;
+
+    fn decode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, DecodeError> {
+        decode_cow_with(input, self)
+    }
+
+    /// 解码字节输入，结果不要求是 UTF-8，见 [`DecodeOptions::decode_bytes`]。
+    fn decode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, DecodeError> {
+        decode_bytes_with(input, self)
+    }
+}
+
+impl Encoder for EncodeOptions {
+    type Error = Infallible;
+
+    fn encode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, Infallible> {
+        Ok(encode_cow_with(input, self))
+    }
+
+    fn encode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, Infallible> {
+        Ok(encode_bytes_with(input, self))
+    }
+}
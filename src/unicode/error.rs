@@ -0,0 +1,67 @@
+use alloc::string::String;
+use core::error::Error;
+use core::fmt;
+
+/// 转义序列解析失败的原因。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeErrorKind {
+    /// `\u`、`\u{...}` 或 `\x` 之后没有足够的十六进制数字。
+    InvalidHex,
+    /// 不支持的转义序列，如 `\q`，或输入以单独的 `\` 结尾。
+    UnknownEscape,
+    /// 没有配对的 UTF-16 代理项，如单独的 `\ud83d`。
+    LoneSurrogate,
+    /// 码点超出 Unicode 范围（大于 `0x10FFFF`），如 `\u{110000}`。
+    OutOfRange,
+    /// 字节输入中存在非法的 UTF-8 序列。
+    InvalidUtf8,
+}
+
+impl fmt::Display for DecodeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DecodeErrorKind::InvalidHex => "缺少有效的十六进制数字",
+            DecodeErrorKind::UnknownEscape => "不支持的转义序列",
+            DecodeErrorKind::LoneSurrogate => "没有配对的 UTF-16 代理项",
+            DecodeErrorKind::OutOfRange => "码点超出 Unicode 范围",
+            DecodeErrorKind::InvalidUtf8 => "不是有效的 UTF-8",
+        })
+    }
+}
+
+/// 解码转义序列时遇到的错误。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::unicode::{try_decode, DecodeErrorKind};
+/// let err = try_decode(r"ok \ud83d bad").unwrap_err();
+/// assert_eq!(err.offset, 3);
+/// assert_eq!(err.escape, r"\ud83d");
+/// assert_eq!(err.kind, DecodeErrorKind::LoneSurrogate);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    /// 出错的转义序列在输入中的字节偏移（指向 `\`）。
+    pub offset: usize,
+    /// 出错的转义序列原文；非法的 UTF-8 序列以 `\xHH` 形式表示。
+    pub escape: String,
+    /// 出错原因。
+    pub kind: DecodeErrorKind,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let what = match self.kind {
+            DecodeErrorKind::InvalidUtf8 => "字节序列",
+            _ => "转义序列",
+        };
+        write!(
+            f,
+            "第 {} 字节处的{what} `{}` 无效：{}",
+            self.offset, self.escape, self.kind
+        )
+    }
+}
+
+impl Error for DecodeError {}
@@ -0,0 +1,506 @@
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use core::fmt::{self, Write};
+
+mod error;
+mod options;
+
+pub use crate::codec::ErrorPolicy;
+pub use error::{DecodeError, DecodeErrorKind};
+pub use options::{DecodeOptions, EncodeMode, EncodeOptions, EncodeStyle};
+
+/// 解码核心实现可能遇到的错误。
+#[derive(Debug)]
+enum Error {
+    Decode(DecodeError),
+    Fmt(fmt::Error),
+}
+
+impl From<fmt::Error> for Error {
+    fn from(e: fmt::Error) -> Self {
+        Error::Fmt(e)
+    }
+}
+
+/// 单个转义序列的解析结果。
+#[derive(Debug, PartialEq)]
+enum Parsed {
+    /// 合法的转义序列。
+    Valid(char),
+    /// 格式不正确的转义序列；非严格模式下按普通文本保留。
+    Malformed(DecodeErrorKind),
+    /// 码点不合法的 `\u` 转义序列。
+    Invalid(DecodeErrorKind),
+}
+
+/// 解析 `\u` 之后的 `XXXX` 或 `{X...}`，返回码元（或码点）及消耗的字节数；失败时同样返回已检查的字节数。
+fn parse_unit(body: &str) -> Result<(u32, usize), (DecodeErrorKind, usize)> {
+    let hex_len = |s: &str, max: usize| {
+        s.bytes()
+            .take(max)
+            .take_while(u8::is_ascii_hexdigit)
+            .count()
+    };
+    if let Some(braced) = body.strip_prefix('{') {
+        let digits = hex_len(braced, usize::MAX);
+        if digits == 0 || !braced[digits..].starts_with('}') {
+            return Err((DecodeErrorKind::InvalidHex, digits + 1));
+        }
+        // 位数过多时同样视为超出范围。
+        let unit = u32::from_str_radix(&braced[..digits], 16).unwrap_or(u32::MAX);
+        return Ok((unit, digits + 2));
+    }
+    match hex_len(body, 4) {
+        4 => Ok((u32::from_str_radix(&body[..4], 16).unwrap_or_default(), 4)),
+        digits => Err((DecodeErrorKind::InvalidHex, digits)),
+    }
+}
+
+/// 解析 `\u` 转义序列，高代理项后紧跟低代理项时合并为一个字符；返回解析结果及消耗的字节数（含 `u`）。
+fn parse_unicode(body: &str) -> (Parsed, usize) {
+    let (unit, len) = match parse_unit(body) {
+        Ok(parsed) => parsed,
+        Err((kind, len)) => return (Parsed::Malformed(kind), len + 1),
+    };
+    let len = len + 1;
+    match unit {
+        0xD800..=0xDBFF => {
+            let low = body[len - 1..]
+                .strip_prefix("\\u")
+                .and_then(|rest| parse_unit(rest).ok());
+            match low {
+                Some((low @ 0xDC00..=0xDFFF, low_len)) => {
+                    let c = 0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00);
+                    let c = char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER);
+                    (Parsed::Valid(c), len + 2 + low_len)
+                }
+                _ => (Parsed::Invalid(DecodeErrorKind::LoneSurrogate), len),
+            }
+        }
+        0xDC00..=0xDFFF => (Parsed::Invalid(DecodeErrorKind::LoneSurrogate), len),
+        _ => match char::from_u32(unit) {
+            Some(c) => (Parsed::Valid(c), len),
+            None => (Parsed::Invalid(DecodeErrorKind::OutOfRange), len),
+        },
+    }
+}
+
+/// 解析 `\` 之后的转义序列，返回解析结果及消耗的字节数（不含 `\`）。
+fn parse_escape(input: &str) -> (Parsed, usize) {
+    let Some(c) = input.chars().next() else {
+        return (Parsed::Malformed(DecodeErrorKind::UnknownEscape), 0);
+    };
+    let c = match c {
+        'u' => return parse_unicode(&input[1..]),
+        'x' => {
+            let hex = input
+                .get(1..3)
+                .filter(|h| h.bytes().all(|b| b.is_ascii_hexdigit()));
+            return match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                Some(b) => (Parsed::Valid(char::from(b)), 3),
+                None => (Parsed::Malformed(DecodeErrorKind::InvalidHex), 1),
+            };
+        }
+        'n' => '\n',
+        't' => '\t',
+        'r' => '\r',
+        'b' => '\u{8}',
+        'f' => '\u{C}',
+        'v' => '\u{B}',
+        // `\0` 后紧跟数字时是 JavaScript 中已废弃的八进制转义。
+        '0' if !input[1..].starts_with(|c: char| c.is_ascii_digit()) => '\0',
+        '"' | '\'' | '\\' | '/' => c,
+        _ => {
+            return (
+                Parsed::Malformed(DecodeErrorKind::UnknownEscape),
+                c.len_utf8(),
+            )
+        }
+    };
+    (Parsed::Valid(c), 1)
+}
+
+/// 按选项解码并返回新分配的字符串。
+fn decode_with(s: &str, options: &DecodeOptions) -> Result<String, DecodeError> {
+    let mut out = String::with_capacity(s.len());
+    decode_string(s, options, &mut out)?;
+    Ok(out)
+}
+
+/// 按选项解码；输入中没有 `\` 时直接借用输入。
+fn decode_cow_with<'s>(s: &'s str, options: &DecodeOptions) -> Result<Cow<'s, str>, DecodeError> {
+    if s.contains('\\') {
+        decode_with(s, options).map(Cow::Owned)
+    } else {
+        Ok(Cow::Borrowed(s))
+    }
+}
+
+/// 按选项解码并将结果追加到 `out`，写入 `String` 不会出现格式化错误。
+fn decode_string(s: &str, options: &DecodeOptions, out: &mut String) -> Result<(), DecodeError> {
+    decode_into(s, options, out).map_err(|e| match e {
+        Error::Decode(e) => e,
+        Error::Fmt(_) => unreachable!("写入 String 不会失败"),
+    })
+}
+
+/// 解码的核心实现，将结果写入 `out`；严格模式下遇到第一个不合规的转义序列即返回错误。
+fn decode_into<W: Write + ?Sized>(
+    s: &str,
+    options: &DecodeOptions,
+    out: &mut W,
+) -> Result<(), Error> {
+    let mut rest = s;
+    while let Some(i) = rest.find('\\') {
+        out.write_str(&rest[..i])?;
+        let offset = s.len() - rest.len() + i;
+        rest = &rest[i + 1..];
+        let (parsed, len) = parse_escape(rest);
+        let escape = &s[offset..=offset + len];
+        match (parsed, options.policy) {
+            (Parsed::Malformed(kind) | Parsed::Invalid(kind), ErrorPolicy::Strict) => {
+                return Err(Error::Decode(DecodeError {
+                    offset,
+                    escape: escape.to_string(),
+                    kind,
+                }));
+            }
+            (Parsed::Valid(c), _) => out.write_char(c)?,
+            (Parsed::Malformed(_), _) => {
+                out.write_char('\\')?;
+                continue;
+            }
+            (Parsed::Invalid(_), ErrorPolicy::Replace) => {
+                out.write_char(char::REPLACEMENT_CHARACTER)?
+            }
+            (Parsed::Invalid(_), ErrorPolicy::Passthrough) => out.write_str(escape)?,
+            (Parsed::Invalid(_), ErrorPolicy::Drop) => {}
+        }
+        rest = &rest[len..];
+    }
+    out.write_str(rest)?;
+    Ok(())
+}
+
+/// 将包含 `\uXXXX` 等转义序列的字符串（如 JavaScript 代码或 JSON 中的字符串）还原为原始字符。
+///
+/// # 说明
+///
+/// 支持以下转义序列，其余文本原样保留：
+///
+/// - `\uXXXX`：UTF-16 码元，相邻的高、低代理项（如 `\ud83d\udc4d`）合并为一个字符。
+/// - `\u{X...}`：ES6 的码点转义，如 `\u{1F44D}`。
+/// - `\xHH`：JavaScript 的 Latin-1 转义。
+/// - 简写：`\n`、`\t`、`\r`、`\b`、`\f`、`\v`、`\0`、`\"`、`\'`、`\\`、`\/`。
+///
+/// 没有配对的代理项和超出 Unicode 范围的码点转换为 U+FFFD（`�`）。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::unicode::decode;
+/// assert_eq!(decode(r"\u6d4b\u8bd5"), "测试");
+/// assert_eq!(decode(r"\ud83d\udc4d \u{1F44D}"), "👍 👍");
+/// assert_eq!(decode(r#"\"a\"\t\\n"#), "\"a\"\t\\n");
+/// assert_eq!(decode(r"\udc4d"), "\u{FFFD}");
+/// ```
+///
+/// # 注意事项
+///
+/// - 格式不正确的转义序列（如 `\u12`、`\q`）会原样保留。
+/// - 需要其他处理方式时，请使用 [`DecodeOptions`]；需要得知解析失败的位置和原因时，请使用 [`try_decode`]。
+pub fn decode(s: &str) -> String {
+    decode_with(s, &DecodeOptions::new()).unwrap_or_default()
+}
+
+/// 严格解码转义序列，遇到格式不正确或码点不合法的转义序列时返回错误。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::unicode::{try_decode, DecodeErrorKind};
+/// assert_eq!(try_decode(r"\u6d4b\u8bd5").unwrap(), "测试");
+/// assert_eq!(try_decode(r"C:\path").unwrap_err().kind, DecodeErrorKind::UnknownEscape);
+/// assert_eq!(try_decode(r"\u{110000}").unwrap_err().kind, DecodeErrorKind::OutOfRange);
+/// ```
+pub fn try_decode(s: &str) -> Result<String, DecodeError> {
+    decode_with(s, &DecodeOptions::new().policy(ErrorPolicy::Strict))
+}
+
+/// 解码转义序列并将结果写入 `out`，不分配中间字符串。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::unicode::decode_to;
+/// let mut out = String::from("> ");
+/// decode_to(r"\u6d4b", &mut out).unwrap();
+/// assert_eq!(out, "> 测");
+/// ```
+pub fn decode_to<W: Write + ?Sized>(s: &str, out: &mut W) -> fmt::Result {
+    decode_into(s, &DecodeOptions::new(), out).map_err(|_| fmt::Error)
+}
+
+/// 解码转义序列，输入中没有 `\` 时直接借用输入。
+///
+/// # 示例
+///
+/// ```rust
+/// # use std::borrow::Cow;
+/// # use utils_rust::unicode::decode_cow;
+/// assert!(matches!(decode_cow("plain"), Cow::Borrowed("plain")));
+/// assert_eq!(decode_cow(r"\u6d4b"), "测");
+/// ```
+pub fn decode_cow(s: &str) -> Cow<'_, str> {
+    decode_cow_with(s, &DecodeOptions::new()).unwrap_or_default()
+}
+
+/// 按选项编码并返回新分配的字符串。
+fn encode_with(s: &str, options: &EncodeOptions) -> String {
+    let mut out = String::with_capacity(s.len());
+    let _ = encode_into(s, options, &mut out);
+    out
+}
+
+/// 按选项编码；没有需要转义的字符时直接借用输入。
+fn encode_cow_with<'s>(s: &'s str, options: &EncodeOptions) -> Cow<'s, str> {
+    if options.mode.find(s).is_some() {
+        Cow::Owned(encode_with(s, options))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+/// 编码的核心实现，将结果写入 `out`。
+fn encode_into<W: Write + ?Sized>(s: &str, options: &EncodeOptions, out: &mut W) -> fmt::Result {
+    let mut start = 0;
+    while let Some(i) = options.mode.find(&s[start..]) {
+        let i = start + i;
+        out.write_str(&s[start..i])?;
+        let c = s[i..].chars().next().unwrap_or_default();
+        start = i + c.len_utf8();
+        write_escape(c, options, out)?;
+    }
+    out.write_str(&s[start..])
+}
+
+/// 将单个字符写为转义序列。
+fn write_escape<W: Write + ?Sized>(c: char, options: &EncodeOptions, out: &mut W) -> fmt::Result {
+    if options.mode == EncodeMode::NonAscii {
+        let short = match c {
+            '\n' => "\\n",
+            '\t' => "\\t",
+            '\r' => "\\r",
+            '\u{8}' => "\\b",
+            '\u{C}' => "\\f",
+            '"' => "\\\"",
+            '\\' => "\\\\",
+            _ => "",
+        };
+        if !short.is_empty() {
+            return out.write_str(short);
+        }
+    }
+    let write_hex = |out: &mut W, n: u32, width: usize| {
+        if options.uppercase {
+            write!(out, "{n:0width$X}")
+        } else {
+            write!(out, "{n:0width$x}")
+        }
+    };
+    if options.style == EncodeStyle::Braced && c > '\u{FFFF}' {
+        out.write_str("\\u{")?;
+        write_hex(out, c as u32, 0)?;
+        return out.write_char('}');
+    }
+    for unit in c.encode_utf16(&mut [0; 2]) {
+        out.write_str("\\u")?;
+        write_hex(out, u32::from(*unit), 4)?;
+    }
+    Ok(())
+}
+
+/// 将字符串中的非 ASCII 字符和必须转义的字符编码为 `\uXXXX` 等转义序列，结果可以直接放入 JSON 字符串或 JavaScript 的双引号字符串字面量。
+///
+/// # 说明
+///
+/// `"`、`\` 和常见的控制字符使用 `\"`、`\\`、`\n`、`\t`、`\r`、`\b`、`\f` 简写，其余控制字符和非 ASCII 字符写为小写十六进制的 `\uXXXX`，辅助平面字符写为 UTF-16 代理对。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::unicode::encode;
+/// assert_eq!(encode("测试"), r"\u6d4b\u8bd5");
+/// assert_eq!(encode("👍"), r"\ud83d\udc4d");
+/// assert_eq!(encode("a \"b\"\n"), r#"a \"b\"\n"#);
+/// assert_eq!(encode("it's"), "it's");
+/// ```
+///
+/// # 注意事项
+///
+/// - `'` 不会被转义，结果不能直接放入 JavaScript 的单引号字符串字面量。
+/// - 需要 ES6 的 `\u{1F44D}` 格式、大写十六进制或转义所有字符时，请使用 [`EncodeOptions`]。
+pub fn encode(s: &str) -> String {
+    encode_with(s, &EncodeOptions::new())
+}
+
+/// 编码字符串并将结果写入 `out`，不分配中间字符串。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::unicode::encode_to;
+/// let mut out = String::from("\"");
+/// encode_to("测", &mut out).unwrap();
+/// out.push('"');
+/// assert_eq!(out, r#""\u6d4b""#);
+/// ```
+pub fn encode_to<W: Write + ?Sized>(s: &str, out: &mut W) -> fmt::Result {
+    encode_into(s, &EncodeOptions::new(), out)
+}
+
+/// 编码字符串，没有需要转义的字符时直接借用输入。
+///
+/// # 示例
+///
+/// ```rust
+/// # use std::borrow::Cow;
+/// # use utils_rust::unicode::encode_cow;
+/// assert!(matches!(encode_cow("plain"), Cow::Borrowed("plain")));
+/// assert_eq!(encode_cow("测"), r"\u6d4b");
+/// ```
+pub fn encode_cow(s: &str) -> Cow<'_, str> {
+    encode_cow_with(s, &EncodeOptions::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode(r"\u6d4b\u8bd5"), "测试");
+        assert_eq!(decode(r"\u6D4B\u8BD5"), "测试");
+        assert_eq!(decode(r"Hello \u6d4b world"), "Hello 测 world");
+        assert_eq!(decode("no escapes"), "no escapes");
+        assert_eq!(decode(r"\x41\x7e"), "A~");
+    }
+
+    #[test]
+    fn test_decode_surrogates() {
+        assert_eq!(decode(r"\ud83d\udc4d"), "👍");
+        assert_eq!(decode(r"\uD83D\uDC4D"), "👍");
+        assert_eq!(decode(r"\u{d83d}\u{dc4d}"), "👍");
+        assert_eq!(decode(r"\ud83d"), "\u{FFFD}");
+        assert_eq!(decode(r"\ud83dx"), "\u{FFFD}x");
+        assert_eq!(decode(r"\udc4d\ud83d"), "\u{FFFD}\u{FFFD}");
+        assert_eq!(decode(r"\ud83d\ud83d\udc4d"), "\u{FFFD}👍");
+        assert_eq!(decode(r"\ud83d\u0041"), "\u{FFFD}A");
+    }
+
+    #[test]
+    fn test_decode_braced() {
+        assert_eq!(decode(r"\u{1F44D}"), "👍");
+        assert_eq!(decode(r"\u{41}\u{0000000041}"), "AA");
+        assert_eq!(decode(r"\u{10FFFF}"), "\u{10FFFF}");
+        assert_eq!(decode(r"\u{110000}"), "\u{FFFD}");
+        assert_eq!(decode(r"\u{FFFFFFFFFF}"), "\u{FFFD}");
+        assert_eq!(decode(r"\u{} \u{41"), r"\u{} \u{41");
+    }
+
+    #[test]
+    fn test_decode_short() {
+        assert_eq!(
+            decode(r#"\n\t\r\b\f\v\0\"\'\\\/"#),
+            "\n\t\r\u{8}\u{C}\u{B}\0\"'\\/"
+        );
+        assert_eq!(decode(r"\\u6d4b"), r"\u6d4b");
+        assert_eq!(decode(r"\01"), r"\01");
+    }
+
+    #[test]
+    fn test_decode_malformed() {
+        assert_eq!(decode(r"\u12 \u12g4 \x4 \q \"), r"\u12 \u12g4 \x4 \q \");
+        assert_eq!(decode(r"C:\path\测"), r"C:\path\测");
+    }
+
+    #[test]
+    fn test_decode_policy() {
+        let input = r"a\ud83db\u{110000}c\q";
+        let decode = |policy| DecodeOptions::new().policy(policy).decode(input);
+        assert_eq!(decode(ErrorPolicy::Drop).unwrap(), r"abc\q");
+        assert_eq!(
+            decode(ErrorPolicy::Replace).unwrap(),
+            "a\u{FFFD}b\u{FFFD}c\\q"
+        );
+        assert_eq!(decode(ErrorPolicy::Passthrough).unwrap(), input);
+        assert!(decode(ErrorPolicy::Strict).is_err());
+    }
+
+    #[test]
+    fn test_try_decode() {
+        let cases = [
+            (r"ab\u12", 2, r"\u12", DecodeErrorKind::InvalidHex),
+            (r"\u{12", 0, r"\u{12", DecodeErrorKind::InvalidHex),
+            (r"\xg", 0, r"\x", DecodeErrorKind::InvalidHex),
+            (r"测\q", 3, r"\q", DecodeErrorKind::UnknownEscape),
+            (r"\测", 0, r"\测", DecodeErrorKind::UnknownEscape),
+            (r"end\", 3, r"\", DecodeErrorKind::UnknownEscape),
+            (r"\udc4d", 0, r"\udc4d", DecodeErrorKind::LoneSurrogate),
+            (r"\u{110000}", 0, r"\u{110000}", DecodeErrorKind::OutOfRange),
+        ];
+        for (input, offset, escape, kind) in cases {
+            let err = try_decode(input).unwrap_err();
+            assert_eq!(err.offset, offset, "{input}");
+            assert_eq!(err.escape, escape, "{input}");
+            assert_eq!(err.kind, kind, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode("测试"), r"\u6d4b\u8bd5");
+        assert_eq!(encode("a👍b"), r"a\ud83d\udc4db");
+        assert_eq!(encode("\"\\\n\t\r\u{8}\u{C}"), r#"\"\\\n\t\r\b\f"#);
+        assert_eq!(encode("\0\u{1F}\u{7F}'/"), r"\u0000\u001f\u007f'/");
+        assert_eq!(encode("plain text"), "plain text");
+    }
+
+    #[test]
+    fn test_encode_options() {
+        let options = EncodeOptions::new().uppercase(true);
+        assert_eq!(options.encode("测👍"), r"\u6D4B\uD83D\uDC4D");
+        let options = options.style(EncodeStyle::Braced);
+        assert_eq!(options.encode("测👍"), r"\u6D4B\u{1F44D}");
+        let options = EncodeOptions::new().mode(EncodeMode::All);
+        assert_eq!(options.encode("a\n\"👍"), r"\u0061\u000a\u0022\ud83d\udc4d");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let text = "测试 👍 \"quoted\" \\ \n\t\r\0\u{7F}\u{80}\u{FFFF}\u{10000}\u{10FFFF}";
+        for mode in [EncodeMode::NonAscii, EncodeMode::All] {
+            for style in [EncodeStyle::Utf16, EncodeStyle::Braced] {
+                for uppercase in [false, true] {
+                    let options = EncodeOptions::new()
+                        .mode(mode)
+                        .style(style)
+                        .uppercase(uppercase);
+                    let encoded = options.encode(text);
+                    assert!(encoded.is_ascii());
+                    assert_eq!(try_decode(&encoded).unwrap(), text);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cow() {
+        assert!(matches!(decode_cow("plain"), Cow::Borrowed(_)));
+        assert!(matches!(encode_cow("plain"), Cow::Borrowed(_)));
+        assert!(matches!(encode_cow("测"), Cow::Owned(_)));
+        let options = EncodeOptions::new().mode(EncodeMode::All);
+        assert!(matches!(options.encode_cow("a"), Cow::Owned(_)));
+        assert!(matches!(options.encode_cow(""), Cow::Borrowed(_)));
+    }
+}
@@ -0,0 +1,200 @@
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use core::fmt::{self, Write};
+use core::str::{self, Utf8Error};
+
+use super::{
+    decode_cow_with, decode_with, encode_cow_with, encode_into, encode_with, DecodeError,
+    DecodeErrorKind, ErrorPolicy,
+};
+use crate::codec::{Decoder, Encoder};
+
+/// 转义序列的解码选项。
+///
+/// # 说明
+///
+/// 默认选项与 [`decode`](super::decode) 的行为一致：没有配对的代理项和超出范围的码点被替换为 U+FFFD，格式不正确的转义序列按原文保留。可以通过 [`DecodeOptions::policy`] 选择其他处理方式。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::unicode::{DecodeOptions, ErrorPolicy};
+/// let input = r"测\ud83d";
+/// let decode = |policy| DecodeOptions::new().policy(policy).decode(input);
+/// assert_eq!(decode(ErrorPolicy::Drop).unwrap(), "测");
+/// assert_eq!(decode(ErrorPolicy::Replace).unwrap(), "测\u{FFFD}");
+/// assert_eq!(decode(ErrorPolicy::Passthrough).unwrap(), r"测\ud83d");
+/// assert!(decode(ErrorPolicy::Strict).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecodeOptions {
+    pub(crate) policy: ErrorPolicy,
+}
+
+impl DecodeOptions {
+    /// 创建默认的解码选项。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置无法解析的转义序列的处理方式，默认为 [`ErrorPolicy::Replace`]：
+    ///
+    /// - [`ErrorPolicy::Strict`]：返回 [`DecodeError`]；十六进制数字不足（如 `\u12`）和不支持的转义（如 `\q`）同样视为错误。
+    /// - [`ErrorPolicy::Replace`]：没有配对的代理项（如 `\ud83d`）和超出范围的码点（如 `\u{110000}`）替换为 U+FFFD（`�`）。
+    /// - [`ErrorPolicy::Passthrough`]：保留这些转义序列的原文，如 `\ud83d`。
+    /// - [`ErrorPolicy::Drop`]：删除这些转义序列。
+    ///
+    /// 非严格模式下，格式不正确的转义序列总是按原文保留。
+    pub fn policy(mut self, policy: ErrorPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// 按当前选项解码字符串，仅在 [`ErrorPolicy::Strict`] 下可能返回错误。
+    pub fn decode(&self, s: &str) -> Result<String, DecodeError> {
+        decode_with(s, self)
+    }
+
+    /// 按当前选项解码字符串，输入中没有 `\` 时直接借用输入。
+    pub fn decode_cow<'s>(&self, s: &'s str) -> Result<Cow<'s, str>, DecodeError> {
+        decode_cow_with(s, self)
+    }
+}
+
+/// 编码时选择需要转义的字符。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EncodeMode {
+    /// 转义非 ASCII 字符，以及在 JSON 字符串和双引号字符串字面量中必须转义的 `"`、`\` 和控制字符。
+    #[default]
+    NonAscii,
+    /// 将所有字符转义为 `\uXXXX`，不使用 `\n` 等简写。
+    All,
+}
+
+impl EncodeMode {
+    /// 判断 ASCII 字节在当前模式下是否需要转义。
+    fn escapes_ascii(&self, b: u8) -> bool {
+        match self {
+            EncodeMode::NonAscii => b.is_ascii_control() || b == b'"' || b == b'\\',
+            EncodeMode::All => true,
+        }
+    }
+
+    /// 查找第一个需要转义的字符的位置。
+    pub(crate) fn find(&self, s: &str) -> Option<usize> {
+        s.bytes()
+            .position(|b| !b.is_ascii() || self.escapes_ascii(b))
+    }
+}
+
+/// 编码时输出的转义格式。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EncodeStyle {
+    /// 按 UTF-16 码元输出 `\uXXXX`，辅助平面字符写为代理对（如 `\ud83d\udc4d`），兼容 JSON 和所有版本的 JavaScript。
+    #[default]
+    Utf16,
+    /// 辅助平面字符写为 ES6 的 `\u{1f44d}`，其余字符仍为 `\uXXXX`。
+    Braced,
+}
+
+/// 转义序列的编码选项。
+///
+/// # 示例
+///
+/// ```rust
+/// # use utils_rust::unicode::{EncodeMode, EncodeOptions, EncodeStyle};
+/// let options = EncodeOptions::new();
+/// assert_eq!(options.encode("\"👍\"\n"), r#"\"\ud83d\udc4d\"\n"#);
+/// assert_eq!(options.style(EncodeStyle::Braced).encode("👍"), r"\u{1f44d}");
+/// assert_eq!(options.uppercase(true).encode("测"), r"\u6D4B");
+/// assert_eq!(options.mode(EncodeMode::All).encode("a\n"), r"\u0061\u000a");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EncodeOptions {
+    pub(crate) mode: EncodeMode,
+    pub(crate) style: EncodeStyle,
+    pub(crate) uppercase: bool,
+}
+
+impl EncodeOptions {
+    /// 创建默认的编码选项。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置需要转义的字符范围。
+    pub fn mode(mut self, mode: EncodeMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// 设置辅助平面字符的转义格式。
+    pub fn style(mut self, style: EncodeStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// 设置十六进制数字是否使用大写字母，默认为小写。
+    pub fn uppercase(mut self, uppercase: bool) -> Self {
+        self.uppercase = uppercase;
+        self
+    }
+
+    /// 按当前选项编码字符串。
+    pub fn encode(&self, s: &str) -> String {
+        encode_with(s, self)
+    }
+
+    /// 按当前选项编码字符串并写入 `out`，不分配中间字符串。
+    pub fn encode_to<W: Write + ?Sized>(&self, s: &str, out: &mut W) -> fmt::Result {
+        encode_into(s, self, out)
+    }
+
+    /// 按当前选项编码字符串，没有需要转义的字符时直接借用输入。
+    pub fn encode_cow<'s>(&self, s: &'s str) -> Cow<'s, str> {
+        encode_cow_with(s, self)
+    }
+}
+
+/// 将文本处理结果转换为字节，结果借用自输入时直接借用。
+fn into_bytes(text: Cow<'_, str>) -> Cow<'_, [u8]> {
+    match text {
+        Cow::Borrowed(s) => Cow::Borrowed(s.as_bytes()),
+        Cow::Owned(s) => Cow::Owned(s.into_bytes()),
+    }
+}
+
+impl Decoder for DecodeOptions {
+    type Error = DecodeError;
+
+    fn decode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, DecodeError> {
+        decode_cow_with(input, self)
+    }
+
+    /// 解码字节输入；输入必须是合法的 UTF-8，否则报告为 [`DecodeErrorKind::InvalidUtf8`]。
+    fn decode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, DecodeError> {
+        let text = str::from_utf8(input).map_err(|e| {
+            let offset = e.valid_up_to();
+            let len = e.error_len().unwrap_or(input.len() - offset);
+            DecodeError {
+                offset,
+                escape: input[offset..offset + len].escape_ascii().to_string(),
+                kind: DecodeErrorKind::InvalidUtf8,
+            }
+        })?;
+        decode_cow_with(text, self).map(into_bytes)
+    }
+}
+
+impl Encoder for EncodeOptions {
+    type Error = Utf8Error;
+
+    fn encode_str<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, Utf8Error> {
+        Ok(encode_cow_with(input, self))
+    }
+
+    /// 编码字节输入；输入必须是合法的 UTF-8。
+    fn encode_bytes<'a>(&self, input: &'a [u8]) -> Result<Cow<'a, [u8]>, Utf8Error> {
+        Ok(into_bytes(encode_cow_with(str::from_utf8(input)?, self)))
+    }
+}
@@ -0,0 +1,147 @@
+//! `ascii` 模块的性质测试：任意 Unicode 文本（含辅助平面字符、组合字符、控制字符以及类似字符引用的片段）编码后都能解码还原，`U+0000` 和被 Windows-1252 重映射的 C1 控制字符除外。
+
+use std::borrow::Cow;
+use std::io::{self, Read};
+
+use proptest::prelude::*;
+use utils_rust::ascii::{
+    decode, decode_bytes, decode_cow, encode, encode_bytes, encode_cow, try_decode, DecodeOptions,
+    EncodeMode, EncodeOptions, EncodeStyle, EntityDecoder, ErrorPolicy,
+};
+
+/// 偏向容易出错的字符的文本片段。
+fn fragment() -> impl Strategy<Value = String> {
+    prop_oneof![
+        any::<char>().prop_map(String::from),
+        // 组合字符
+        prop::char::range('\u{0300}', '\u{036F}').prop_map(String::from),
+        // C0、C1 控制字符
+        prop::char::ranges(vec!['\0'..='\u{1F}', '\u{7F}'..='\u{9F}'].into())
+            .prop_map(String::from),
+        // 辅助平面字符
+        prop::char::range('\u{10000}', '\u{10FFFF}').prop_map(String::from),
+        // HTML 特殊字符和字符引用片段
+        prop::sample::select(vec![
+            "<",
+            ">",
+            "&",
+            "\"",
+            "'",
+            ";",
+            "#",
+            "&amp;",
+            "&amp",
+            "&#",
+            "&#x",
+            "&#38;",
+            "&#0;",
+            "&#xD800;",
+            "&notin",
+            "&not",
+            "&NotEqualTilde;",
+            "&foo;",
+        ])
+        .prop_map(String::from),
+        "[a-zA-Z0-9]{1,4}",
+    ]
+}
+
+/// 编码后能解码还原的字符：按 WHATWG 规则，`&#0;` 解码为 U+FFFD，`&#128;`～`&#159;` 中的大部分解码为 Windows-1252 字符，这些字符的映射由单元测试检查。
+fn representable(c: char) -> bool {
+    match c {
+        '\0' => false,
+        '\u{80}'..='\u{9F}' => matches!(c, '\u{81}' | '\u{8D}' | '\u{8F}' | '\u{90}' | '\u{9D}'),
+        _ => true,
+    }
+}
+
+/// 去掉无法还原的字符。
+fn strip(s: String) -> String {
+    s.chars().filter(|&c| representable(c)).collect()
+}
+
+fn text() -> impl Strategy<Value = String> {
+    prop::collection::vec(fragment(), 0..32).prop_map(|parts| parts.concat())
+}
+
+/// 编码后能解码还原的文本。
+fn round_trip_text() -> impl Strategy<Value = String> {
+    text().prop_map(strip)
+}
+
+fn style() -> impl Strategy<Value = EncodeStyle> {
+    prop::sample::select(vec![
+        EncodeStyle::Decimal,
+        EncodeStyle::HexLower,
+        EncodeStyle::HexUpper,
+        EncodeStyle::Named,
+    ])
+}
+
+/// 每次最多读取 `chunk` 字节的读取器，用于检查流式解码在任意分块下的结果。
+struct Chunked<'a> {
+    data: &'a [u8],
+    chunk: usize,
+}
+
+impl Read for Chunked<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.chunk.min(buf.len()).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_round_trip(s in round_trip_text()) {
+        prop_assert_eq!(decode(&encode(&s)), s.as_str());
+        prop_assert_eq!(try_decode(&encode(&s)).unwrap(), s);
+    }
+
+    #[test]
+    fn test_round_trip_any_string(s in any::<String>().prop_map(strip)) {
+        prop_assert_eq!(decode(&encode(&s)), s);
+    }
+
+    #[test]
+    fn test_round_trip_options(s in round_trip_text(), style in style(), special in any::<bool>()) {
+        let mode = if special { EncodeMode::HtmlSpecial } else { EncodeMode::All };
+        let encoded = EncodeOptions::new().mode(mode).style(style).encode(&s);
+        let strict = DecodeOptions::new().policy(ErrorPolicy::Strict);
+        prop_assert_eq!(strict.decode(&encoded).unwrap(), s);
+    }
+
+    #[test]
+    fn test_round_trip_bytes(bytes in prop::collection::vec(any::<u8>(), 0..64)) {
+        let expected = String::from_utf8_lossy(&bytes);
+        prop_assume!(expected.chars().all(representable));
+        let encoded = encode_bytes(&bytes);
+        prop_assert_eq!(decode_bytes(&encoded), expected.as_bytes());
+    }
+
+    #[test]
+    fn test_cow_matches_owned(s in text()) {
+        prop_assert_eq!(decode_cow(&s), decode(&s));
+        prop_assert_eq!(encode_cow(&s), encode(&s));
+        if !s.contains('&') {
+            prop_assert!(matches!(decode_cow(&s), Cow::Borrowed(_)));
+        }
+    }
+
+    #[test]
+    fn test_try_decode_matches_decode(s in text()) {
+        if let Ok(decoded) = try_decode(&s) {
+            prop_assert_eq!(decoded, decode(&s));
+        }
+    }
+
+    #[test]
+    fn test_stream_matches_decode(s in text(), chunk in 1usize..16) {
+        let mut decoder = EntityDecoder::new(Chunked { data: s.as_bytes(), chunk });
+        let mut out = String::new();
+        decoder.read_to_string(&mut out).unwrap();
+        prop_assert_eq!(out, decode(&s));
+    }
+}
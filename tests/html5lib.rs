@@ -0,0 +1,153 @@
+//! 与 html5lib 分词器测试向量的差分测试：数据状态下只含文本的用例，分词器输出的字符应与 `ascii::decode` 的结果一致；属性值用例按 [`DecodeOptions::attribute`] 解码后应与分词器得到的属性值一致。
+//!
+//! 测试向量位于 `tests/data/html5lib`，格式见该目录下的 `README.md`。
+
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+use utils_rust::ascii::{DecodeOptions, ErrorPolicy};
+
+/// 还原 `doubleEscaped` 用例中以 `\uXXXX` 转义的字符，代理对合并为一个字符。
+fn unescape(s: &str) -> String {
+    let units = s.encode_utf16().collect::<Vec<_>>();
+    let mut out = Vec::with_capacity(units.len());
+    let mut i = 0;
+    while i < units.len() {
+        let hex = String::from_utf16_lossy(units.get(i + 2..i + 6).unwrap_or_default());
+        match u16::from_str_radix(&hex, 16) {
+            Ok(unit) if units[i] == u16::from(b'\\') && units[i + 1] == u16::from(b'u') => {
+                out.push(unit);
+                i += 6;
+            }
+            _ => {
+                out.push(units[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf16_lossy(&out)
+}
+
+/// 可以与 `decode` 对比的用例。
+struct Case<'a> {
+    description: &'a str,
+    input: String,
+    expected: String,
+    /// 输入是否为 `<h a='...'>` 形式标签中的属性值。
+    attribute: bool,
+    has_errors: bool,
+}
+
+/// 取出 `<h a='...'>`、`<h a="...">` 或 `<h a=...>` 形式的标签中的属性值。
+fn attribute_value(input: &str) -> Option<&str> {
+    let value = input.strip_prefix("<h a=")?.strip_suffix('>')?;
+    for quote in ['\'', '"'] {
+        if let Some(value) = value.strip_prefix(quote) {
+            return value.strip_suffix(quote);
+        }
+    }
+    Some(value)
+}
+
+/// 数据状态下只含文本的用例，或只含一个带属性 `a` 的 `h` 标签的用例。
+fn parse_case(test: &Value) -> Option<Case<'_>> {
+    if let Some(states) = test.get("initialStates") {
+        if !states.as_array()?.iter().any(|s| s == "Data state") {
+            return None;
+        }
+    }
+    let double_escaped = test.get("doubleEscaped") == Some(&Value::Bool(true));
+    let fix = |s: &str| {
+        if double_escaped {
+            unescape(s)
+        } else {
+            s.to_string()
+        }
+    };
+    let input = fix(test["input"].as_str()?);
+    if input.contains('\r') {
+        return None;
+    }
+    // 较早的测试文件把解析错误记为输出中的 `"ParseError"`，较新的文件记在 `errors` 中。
+    let (parse_errors, output): (Vec<&Value>, Vec<&Value>) = test["output"]
+        .as_array()?
+        .iter()
+        .partition(|token| *token == "ParseError");
+    let (input, expected, attribute) = match output.first()?.as_array()?.as_slice() {
+        [kind, name, attrs] if kind == "StartTag" && name == "h" && output.len() == 1 => {
+            let value = attribute_value(&input)?;
+            let attrs = attrs.as_object()?;
+            if attrs.len() != 1 || value.contains(['<', '>', '\'', '"']) {
+                return None;
+            }
+            (value.to_string(), fix(attrs.get("a")?.as_str()?), true)
+        }
+        _ if !input.contains('<') => {
+            let mut expected = String::new();
+            for token in &output {
+                match token.as_array()?.as_slice() {
+                    [kind, text] if kind == "Character" => expected.push_str(&fix(text.as_str()?)),
+                    _ => return None,
+                }
+            }
+            (input, expected, false)
+        }
+        _ => return None,
+    };
+    let has_errors = !parse_errors.is_empty()
+        || test
+            .get("errors")
+            .and_then(Value::as_array)
+            .is_some_and(|errors| !errors.is_empty());
+    Some(Case {
+        description: test["description"].as_str()?,
+        input,
+        expected,
+        attribute,
+        has_errors,
+    })
+}
+
+#[test]
+fn test_html5lib_vectors() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/html5lib");
+    let mut checked = [0; 2];
+    let mut failures = Vec::new();
+
+    for entry in fs::read_dir(&dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension() != Some("test".as_ref()) {
+            continue;
+        }
+        let json: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        for test in json["tests"].as_array().unwrap() {
+            let Some(case) = parse_case(test) else {
+                continue;
+            };
+            checked[usize::from(case.attribute)] += 1;
+            let options = DecodeOptions::new().attribute(case.attribute);
+            let Case {
+                description,
+                input,
+                expected,
+                ..
+            } = &case;
+            let actual = options.decode(input).unwrap();
+            if actual != *expected {
+                failures.push(format!(
+                    "{description}: {input:?} => {actual:?}，期望 {expected:?}"
+                ));
+            }
+            // 没有解析错误的输入在严格模式下同样不应报错。
+            let strict = options.policy(ErrorPolicy::Strict);
+            if !case.has_errors && strict.decode(input).as_ref() != Ok(expected) {
+                failures.push(format!("{description}: 严格模式下解码 {input:?} 失败"));
+            }
+        }
+    }
+
+    assert!(checked[0] >= 4000, "只检查了 {} 个文本用例", checked[0]);
+    assert!(checked[1] >= 2, "只检查了 {} 个属性值用例", checked[1]);
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
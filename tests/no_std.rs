@@ -0,0 +1,73 @@
+//! 确认关闭 `std` 特性后，crate 仍能以 `#![no_std]` 编译。
+//!
+//! 在宿主平台上检查可以证明代码没有使用 `std`，针对裸机目标 `thumbv7em-none-eabihf` 检查可以证明 crate 能在没有标准库的平台上编译。后者需要先运行 `rustup target add thumbv7em-none-eabihf` 安装该目标，未安装时测试失败。
+//!
+//! 检查时启用 `-D warnings`，只在某些特性组合下才使用的代码（如仅供 `std` 使用的常量）未加条件编译时会报告为错误。
+
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+/// 用于检查的裸机目标，只包含 `core` 和 `alloc`。
+const NO_STD_TARGET: &str = "thumbv7em-none-eabihf";
+
+fn cargo_check(features: &[&str], target: Option<&str>) {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut command = Command::new(cargo);
+    command
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env_remove("CARGO_ENCODED_RUSTFLAGS")
+        .env("RUSTFLAGS", "-D warnings")
+        .args(["check", "--lib", "--quiet", "--no-default-features"])
+        .args([
+            "--target-dir",
+            concat!(env!("CARGO_TARGET_TMPDIR"), "/no_std"),
+        ]);
+    if !features.is_empty() {
+        command.args(["--features", &features.join(",")]);
+    }
+    if let Some(target) = target {
+        command.args(["--target", target]);
+    }
+    let status = command.status().expect("无法运行 cargo");
+    assert!(
+        status.success(),
+        "features = {features:?}, target = {target:?}"
+    );
+}
+
+/// 判断是否安装了指定目标的标准库组件。
+fn target_installed(target: &str) -> bool {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    Command::new(rustc)
+        .args(["--print", "target-libdir", "--target", target])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| Path::new(String::from_utf8_lossy(&output.stdout).trim()).exists())
+        .unwrap_or(false)
+}
+
+#[test]
+fn test_no_std_core() {
+    cargo_check(&[], None);
+}
+
+#[test]
+fn test_no_std_alloc() {
+    cargo_check(&["alloc"], None);
+}
+
+#[test]
+fn test_no_std_serde() {
+    cargo_check(&["serde"], None);
+}
+
+#[test]
+fn test_no_std_target() {
+    assert!(
+        target_installed(NO_STD_TARGET),
+        "未安装 {NO_STD_TARGET}，请先运行 `rustup target add {NO_STD_TARGET}`"
+    );
+    cargo_check(&["alloc"], Some(NO_STD_TARGET));
+}